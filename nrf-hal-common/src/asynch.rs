@@ -0,0 +1,67 @@
+//! Helpers shared by the async drivers.
+
+use core::cell::Cell;
+use core::task::Waker;
+use cortex_m::interrupt::{self, Mutex};
+
+/// Storage for the waker of a task waiting on an interrupt.
+///
+/// The waker is registered from a future's `poll` and taken by the interrupt handler of the
+/// peripheral, which wakes the task up.
+pub(crate) struct AtomicWaker {
+    waker: Mutex<Cell<Option<Waker>>>,
+}
+
+impl AtomicWaker {
+    pub const fn new() -> Self {
+        Self {
+            waker: Mutex::new(Cell::new(None)),
+        }
+    }
+
+    /// Registers `waker`, replacing any previously registered waker.
+    pub fn register(&self, waker: &Waker) {
+        interrupt::free(|cs| {
+            let cell = self.waker.borrow(cs);
+            let waker = match cell.take() {
+                Some(current) if current.will_wake(waker) => current,
+                _ => waker.clone(),
+            };
+            cell.set(Some(waker));
+        });
+    }
+
+    /// Wakes the registered waker, if any.
+    pub fn wake(&self) {
+        if let Some(waker) = interrupt::free(|cs| self.waker.borrow(cs).take()) {
+            waker.wake();
+        }
+    }
+}
+
+/// Runs a closure when dropped, unless it was defused.
+///
+/// Async drivers use this to stop an ongoing EasyDMA transfer when their future is dropped before
+/// completion, so the peripheral never writes to a buffer that is no longer borrowed.
+pub(crate) struct OnDrop<F: FnOnce()> {
+    f: Option<F>,
+}
+
+impl<F: FnOnce()> OnDrop<F> {
+    pub fn new(f: F) -> Self {
+        Self { f: Some(f) }
+    }
+
+    /// Forgets the closure without running it.
+    pub fn defuse(mut self) {
+        self.f = None;
+    }
+}
+
+impl<F: FnOnce()> Drop for OnDrop<F> {
+    fn drop(&mut self) {
+        if let Some(f) = self.f.take() {
+            f();
+        }
+    }
+}
@@ -0,0 +1,704 @@
+//! Buffered, interrupt-driven interface to the UARTE peripheral.
+//!
+//! Unlike [`Uarte`], which only receives while a read is in progress, [`BufferedUarte`] keeps the
+//! receiver running continuously into a ring buffer, so no byte is lost between two reads.
+//!
+//! The RX ring buffer is split in two halves that EasyDMA fills alternately: when a half starts
+//! (`RXSTARTED`), the next half is queued and the `ENDRX_STARTRX` short switches to it without any
+//! gap. The number of received bytes is counted exactly by a TIMER in counter mode, whose `COUNT`
+//! task is triggered by the `RXDRDY` event through a PPI channel, so bytes can be read before a
+//! half is full. When the reader falls behind and the ring buffer is full, the receiver is paused
+//! until data is read; use hardware flow control to avoid losing data in that case.
+//!
+//! Transmission is also buffered: writes are copied into a TX ring buffer, which is sent in the
+//! background.
+//!
+//! The driver is interrupt driven: [`BufferedUarte::on_interrupt`] must be called from the
+//! interrupt handler of the UARTE instance, and the interrupt must be unmasked in the NVIC.
+
+use core::cmp::min;
+use core::hint::spin_loop;
+use core::ptr;
+use core::sync::atomic::{compiler_fence, Ordering::SeqCst};
+use cortex_m::interrupt;
+use embedded_io::{ErrorType, ReadReady, WriteReady};
+
+#[cfg(any(feature = "52833", feature = "52840"))]
+use crate::pac::UARTE1;
+use crate::pac::{uarte0, UARTE0};
+
+use crate::ppi::ConfigurablePpi;
+use crate::target_constants::EASY_DMA_SIZE;
+use crate::timer;
+use crate::uarte::{self, Error, Uarte};
+
+#[cfg(feature = "embedded-io-async")]
+use core::{future::poll_fn, task::Poll};
+
+/// Buffered interface to a UARTE instance.
+///
+/// See the [module documentation](self) for details.
+pub struct BufferedUarte<U, T, P> {
+    uarte: Uarte<U>,
+    timer: T,
+    ppi: P,
+}
+
+impl<U, T, P> BufferedUarte<U, T, P>
+where
+    U: Instance,
+    T: timer::Instance,
+    P: ConfigurablePpi,
+{
+    /// Starts buffered operation of an already configured `uarte`.
+    ///
+    /// `timer` is used in counter mode and `ppi` connects the `RXDRDY` event of the UARTE to the
+    /// `COUNT` task of the timer; both are reserved for the `BufferedUarte` until it is freed.
+    ///
+    /// The length of `rx_buf` must be a power of two, and each half of it must fit in a single
+    /// EasyDMA transfer (at most `2 * EASY_DMA_SIZE` bytes). `tx_buf` can have any length.
+    ///
+    /// Reception starts right away. [`on_interrupt`](Self::on_interrupt) must be called from the
+    /// UARTE interrupt handler from now on.
+    pub fn new(
+        uarte: Uarte<U>,
+        timer: T,
+        mut ppi: P,
+        rx_buf: &'static mut [u8],
+        tx_buf: &'static mut [u8],
+    ) -> Result<Self, Error> {
+        if rx_buf.len() < 2 {
+            return Err(Error::RxBufferTooSmall);
+        }
+        if rx_buf.len() > 2 * EASY_DMA_SIZE {
+            return Err(Error::RxBufferTooLong);
+        }
+        if !rx_buf.len().is_power_of_two() {
+            return Err(Error::RxBufferNotPowerOfTwo);
+        }
+        if tx_buf.is_empty() {
+            return Err(Error::TxBufferTooSmall);
+        }
+
+        let r = U::regs();
+        let s = U::state();
+
+        // Count received bytes in hardware.
+        let t = timer.as_timer0();
+        t.tasks_stop.write(|w| unsafe { w.bits(1) });
+        t.mode.write(|w| w.mode().counter());
+        t.bitmode.write(|w| w.bitmode()._32bit());
+        t.tasks_clear.write(|w| unsafe { w.bits(1) });
+        t.tasks_start.write(|w| unsafe { w.bits(1) });
+
+        ppi.set_event_endpoint(&r.events_rxdrdy);
+        ppi.set_task_endpoint(&t.tasks_count);
+        ppi.enable();
+
+        s.init(rx_buf, tx_buf);
+
+        r.events_endrx.reset();
+        r.events_rxstarted.reset();
+        r.events_endtx.reset();
+        r.events_error.reset();
+        let errors = r.errorsrc.read().bits();
+        // NB Safety: safe to write back the bits we just read to clear them
+        r.errorsrc.write(|w| unsafe { w.bits(errors) });
+
+        r.intenset.write(|w| {
+            w.endrx().set_bit();
+            w.rxstarted().set_bit();
+            w.endtx().set_bit();
+            w.error().set_bit();
+            w
+        });
+
+        interrupt::free(|_| {
+            rx_queue(r, s);
+            r.tasks_startrx.write(|w| unsafe { w.bits(1) });
+        });
+
+        Ok(Self { uarte, timer, ppi })
+    }
+
+    /// Stops buffered operation and returns the UARTE, timer and PPI channel.
+    ///
+    /// This waits for the buffered TX data to be sent. Received data that was not read yet is
+    /// discarded.
+    pub fn free(mut self) -> (Uarte<U>, T, P) {
+        self.flush_blocking();
+
+        let r = U::regs();
+        r.intenclr.write(|w| {
+            w.endrx().set_bit();
+            w.rxstarted().set_bit();
+            w.endtx().set_bit();
+            w.error().set_bit();
+            w.rxdrdy().set_bit();
+            w
+        });
+        r.shorts.reset();
+
+        // The receiver is paused, with no transfer to stop, when no half of the RX buffer was
+        // queued after the last one ended, including an `ENDRX` event not handled yet.
+        let s = U::state();
+        let rx_running = interrupt::free(|_| {
+            let rx_ended = s.rx_ended.get().wrapping_add(r.events_endrx.read().bits());
+            s.rx_queued.get() != rx_ended
+        });
+
+        r.tasks_stoprx.write(|w| unsafe { w.bits(1) });
+        if rx_running {
+            while r.events_rxto.read().bits() == 0 {}
+        }
+        r.events_rxto.reset();
+        r.events_endrx.reset();
+        r.events_rxstarted.reset();
+
+        r.tasks_stoptx.write(|w| unsafe { w.bits(1) });
+        while r.events_txstopped.read().bits() == 0 {}
+        r.events_txstopped.reset();
+        r.events_endtx.reset();
+        compiler_fence(SeqCst);
+
+        self.ppi.disable();
+        self.timer
+            .as_timer0()
+            .tasks_stop
+            .write(|w| unsafe { w.bits(1) });
+
+        (self.uarte, self.timer, self.ppi)
+    }
+
+    /// Returns the position up to which received data is available in the RX ring buffer.
+    fn rx_written(&self) -> u32 {
+        let s = U::state();
+        let received = self.timer.read_counter();
+
+        // While the receiver is paused, bytes are counted when they enter the FIFO, before they
+        // are moved to RAM. They can only be in RAM up to the end of the last started half.
+        let limit = s.rx_started.get().wrapping_mul(s.rx_half());
+        if (received.wrapping_sub(limit) as i32) > 0 {
+            limit
+        } else {
+            received
+        }
+    }
+
+    /// Returns the number of bytes that can be read without waiting.
+    fn rx_available(&self) -> usize {
+        self.rx_written().wrapping_sub(U::state().rx_read.get()) as usize
+    }
+
+    /// Takes a pending receive error, if any.
+    fn rx_take_error(&mut self) -> Result<(), Error> {
+        let s = U::state();
+        let error = interrupt::free(|_| {
+            let error = s.rx_error.get();
+            s.rx_error.set(false);
+            error
+        });
+        if error {
+            self.rx_reset();
+            return Err(Error::Receive);
+        }
+        Ok(())
+    }
+
+    /// Restarts the reception from scratch, discarding the data in the RX ring buffer.
+    fn rx_reset(&mut self) {
+        let r = U::regs();
+        let s = U::state();
+
+        interrupt::free(|_| {
+            r.shorts.modify(|_, w| w.endrx_startrx().disabled());
+
+            r.tasks_stoprx.write(|w| unsafe { w.bits(1) });
+            while r.events_rxto.read().bits() == 0 {}
+            r.events_rxto.reset();
+
+            // Empty the FIFO, its content was counted before the counter is cleared below.
+            r.events_endrx.reset();
+            r.tasks_flushrx.write(|w| unsafe { w.bits(1) });
+            while r.events_endrx.read().bits() == 0 {}
+            r.events_endrx.reset();
+            r.events_rxstarted.reset();
+            compiler_fence(SeqCst);
+
+            self.timer
+                .as_timer0()
+                .tasks_clear
+                .write(|w| unsafe { w.bits(1) });
+            s.rx_reset();
+
+            rx_queue(r, s);
+            r.tasks_startrx.write(|w| unsafe { w.bits(1) });
+        });
+    }
+
+    /// Reads the available data into `buf`, without waiting.
+    fn try_read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        self.rx_take_error()?;
+
+        let r = U::regs();
+        let s = U::state();
+        let read = s.rx_read.get();
+        let n = min(self.rx_available(), buf.len());
+
+        // The data may wrap around the end of the ring buffer.
+        compiler_fence(SeqCst);
+        let start = read as usize & (s.rx_len() - 1);
+        let first = min(n, s.rx_len() - start);
+        unsafe {
+            ptr::copy_nonoverlapping(s.rx_ptr().add(start), buf.as_mut_ptr(), first);
+            ptr::copy_nonoverlapping(s.rx_ptr(), buf[first..].as_mut_ptr(), n - first);
+        }
+        compiler_fence(SeqCst);
+
+        interrupt::free(|_| {
+            s.rx_read.set(read.wrapping_add(n as u32));
+
+            // Restart the receiver if it was paused because the buffer was full. If it is still
+            // running, the interrupt handler queues the next half when the current one ends.
+            if s.rx_queued.get() == s.rx_ended.get() && rx_queue(r, s) {
+                r.tasks_startrx.write(|w| unsafe { w.bits(1) });
+            }
+        });
+
+        Ok(n)
+    }
+
+    /// Copies as much of `buf` as fits into the TX ring buffer, without waiting.
+    fn try_write(&mut self, buf: &[u8]) -> usize {
+        let r = U::regs();
+        let s = U::state();
+        let (start, count) = interrupt::free(|_| (s.tx_start.get(), s.tx_count.get()));
+        let len = s.tx_len();
+        let n = min(len - count, buf.len());
+
+        // The interrupt handler only consumes queued data, so the free space can be filled
+        // outside of the critical section.
+        let end = (start + count) % len;
+        let first = min(n, len - end);
+        unsafe {
+            ptr::copy_nonoverlapping(buf.as_ptr(), s.tx_ptr().add(end), first);
+            ptr::copy_nonoverlapping(buf[first..].as_ptr(), s.tx_ptr(), n - first);
+        }
+
+        interrupt::free(|_| {
+            s.tx_count.set(s.tx_count.get() + n);
+            tx_start(r, s);
+        });
+
+        n
+    }
+
+    /// Returns `true` if all the data in the TX ring buffer was sent.
+    fn tx_done(&self) -> bool {
+        let s = U::state();
+        interrupt::free(|_| s.tx_count.get() == 0)
+    }
+
+    fn flush_blocking(&mut self) {
+        while !self.tx_done() {
+            spin_loop();
+        }
+    }
+}
+
+impl<U, T, P> BufferedUarte<U, T, P>
+where
+    U: Instance,
+{
+    /// Handles the UARTE interrupt for buffered operation.
+    ///
+    /// This must be called from the interrupt handler of the UARTE instance (for example
+    /// `UARTE0_UART0` for UARTE0) while a [`BufferedUarte`] is in use.
+    pub fn on_interrupt() {
+        let r = U::regs();
+        let s = U::state();
+
+        if r.events_error.read().bits() != 0 {
+            r.events_error.reset();
+            let errors = r.errorsrc.read().bits();
+            // NB Safety: safe to write back the bits we just read to clear them
+            r.errorsrc.write(|w| unsafe { w.bits(errors) });
+            s.rx_error.set(true);
+            #[cfg(feature = "embedded-io-async")]
+            s.rx_waker.wake();
+        }
+
+        if r.events_endrx.read().bits() != 0 {
+            r.events_endrx.reset();
+            s.rx_ended.set(s.rx_ended.get().wrapping_add(1));
+
+            // No half was queued when the previous one started: the receiver is now paused, restart
+            // it if the reader has made room since.
+            if s.rx_queued.get() == s.rx_ended.get() && rx_queue(r, s) {
+                r.tasks_startrx.write(|w| unsafe { w.bits(1) });
+            }
+            #[cfg(feature = "embedded-io-async")]
+            s.rx_waker.wake();
+        }
+
+        if r.events_rxstarted.read().bits() != 0 {
+            r.events_rxstarted.reset();
+            s.rx_started.set(s.rx_started.get().wrapping_add(1));
+
+            // The half that just started has latched the DMA pointer, queue the next one. If the
+            // reader is too slow, let the receiver stop at the end of the current half instead.
+            r.shorts.modify(|_, w| w.endrx_startrx().disabled());
+            if rx_queue(r, s) {
+                r.shorts.modify(|_, w| w.endrx_startrx().enabled());
+            }
+            #[cfg(feature = "embedded-io-async")]
+            s.rx_waker.wake();
+        }
+
+        if r.events_rxdrdy.read().bits() != 0 && r.intenset.read().rxdrdy().bit_is_set() {
+            // Only enabled while an async read is waiting for data.
+            r.intenclr.write(|w| w.rxdrdy().set_bit());
+            r.events_rxdrdy.reset();
+            #[cfg(feature = "embedded-io-async")]
+            s.rx_waker.wake();
+        }
+
+        if r.events_endtx.read().bits() != 0 {
+            r.events_endtx.reset();
+            compiler_fence(SeqCst);
+            let sent = s.tx_busy.get();
+            s.tx_start.set((s.tx_start.get() + sent) % s.tx_len());
+            s.tx_count.set(s.tx_count.get() - sent);
+            s.tx_busy.set(0);
+            tx_start(r, s);
+            #[cfg(feature = "embedded-io-async")]
+            s.tx_waker.wake();
+        }
+    }
+}
+
+/// Points the receiver at the next half of the RX ring buffer, if the data it last held was read.
+///
+/// Returns `false` if the reader has to catch up first. Must not be preempted by the interrupt
+/// handler.
+fn rx_queue(r: &uarte0::RegisterBlock, s: &State) -> bool {
+    let queued = s.rx_queued.get();
+    let half = s.rx_half();
+
+    // Half number `queued` reuses the memory of half `queued - 2`, which ends where half
+    // `queued - 1` begins.
+    let reusable_from = queued.wrapping_sub(1).wrapping_mul(half);
+    if (s.rx_read.get().wrapping_sub(reusable_from) as i32) < 0 {
+        return false;
+    }
+
+    let offset = (queued as usize & 1) * half as usize;
+    compiler_fence(SeqCst);
+    r.rxd
+        .ptr
+        .write(|w| unsafe { w.ptr().bits(s.rx_ptr() as u32 + offset as u32) });
+    r.rxd.maxcnt.write(|w|
+        // The buffer length was checked in `new`, so a half always fits in a single transfer.
+        unsafe { w.maxcnt().bits(half as _) });
+    s.rx_queued.set(queued.wrapping_add(1));
+    true
+}
+
+/// Starts sending the next contiguous chunk of the TX ring buffer, if idle.
+///
+/// Must not be preempted by the interrupt handler.
+fn tx_start(r: &uarte0::RegisterBlock, s: &State) {
+    let count = s.tx_count.get();
+    if s.tx_busy.get() != 0 || count == 0 {
+        return;
+    }
+
+    let start = s.tx_start.get();
+    let n = min(min(count, s.tx_len() - start), EASY_DMA_SIZE);
+
+    compiler_fence(SeqCst);
+    r.txd
+        .ptr
+        .write(|w| unsafe { w.ptr().bits(s.tx_ptr() as u32 + start as u32) });
+    r.txd.maxcnt.write(|w| unsafe { w.maxcnt().bits(n as _) });
+    s.tx_busy.set(n);
+    r.tasks_starttx.write(|w| unsafe { w.bits(1) });
+}
+
+impl<U, T, P> ErrorType for BufferedUarte<U, T, P> {
+    type Error = Error;
+}
+
+impl<U, T, P> embedded_io::Read for BufferedUarte<U, T, P>
+where
+    U: Instance,
+    T: timer::Instance,
+    P: ConfigurablePpi,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            match self.try_read(buf)? {
+                0 => spin_loop(),
+                n => return Ok(n),
+            }
+        }
+    }
+}
+
+impl<U, T, P> ReadReady for BufferedUarte<U, T, P>
+where
+    U: Instance,
+    T: timer::Instance,
+    P: ConfigurablePpi,
+{
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        self.rx_take_error()?;
+        Ok(self.rx_available() != 0)
+    }
+}
+
+impl<U, T, P> embedded_io::Write for BufferedUarte<U, T, P>
+where
+    U: Instance,
+    T: timer::Instance,
+    P: ConfigurablePpi,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            match self.try_write(buf) {
+                0 => spin_loop(),
+                n => return Ok(n),
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.flush_blocking();
+        Ok(())
+    }
+}
+
+impl<U, T, P> WriteReady for BufferedUarte<U, T, P>
+where
+    U: Instance,
+    T: timer::Instance,
+    P: ConfigurablePpi,
+{
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        let s = U::state();
+        Ok(interrupt::free(|_| s.tx_count.get() < s.tx_len()))
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+impl<U, T, P> embedded_io_async::Read for BufferedUarte<U, T, P>
+where
+    U: Instance,
+    T: timer::Instance,
+    P: ConfigurablePpi,
+{
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let r = U::regs();
+        poll_fn(|cx| {
+            U::state().rx_waker.register(cx.waker());
+
+            // Get woken up by the next byte. Bytes received from now on are counted, so checking
+            // for data afterwards cannot miss any.
+            r.events_rxdrdy.reset();
+            r.intenset.write(|w| w.rxdrdy().set_bit());
+
+            match self.try_read(buf) {
+                Ok(0) => Poll::Pending,
+                result => {
+                    r.intenclr.write(|w| w.rxdrdy().set_bit());
+                    Poll::Ready(result)
+                }
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+impl<U, T, P> embedded_io_async::Write for BufferedUarte<U, T, P>
+where
+    U: Instance,
+    T: timer::Instance,
+    P: ConfigurablePpi,
+{
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        poll_fn(|cx| {
+            U::state().tx_waker.register(cx.waker());
+            match self.try_write(buf) {
+                0 => Poll::Pending,
+                n => Poll::Ready(Ok(n)),
+            }
+        })
+        .await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        poll_fn(|cx| {
+            U::state().tx_waker.register(cx.waker());
+            if self.tx_done() {
+                Poll::Ready(Ok(()))
+            } else {
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+/// Implemented by the UARTE instances that support buffered operation.
+pub trait Instance: uarte::Instance + sealed::Sealed {}
+
+mod sealed {
+    use core::cell::Cell;
+    use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering::Relaxed};
+
+    #[cfg(feature = "embedded-io-async")]
+    use crate::asynch::AtomicWaker;
+
+    pub trait Sealed {
+        fn regs() -> &'static super::uarte0::RegisterBlock;
+        fn state() -> &'static State;
+    }
+
+    /// State shared between a `BufferedUarte` and the interrupt handler.
+    ///
+    /// Positions in the RX ring buffer count bytes since reception started and wrap around at
+    /// 2^32, which is a multiple of the (power of two) buffer length. The `Cell`s are only
+    /// accessed with interrupts disabled or from the interrupt handler.
+    pub struct State {
+        rx_ptr: AtomicPtr<u8>,
+        rx_len: AtomicUsize,
+        /// Position up to which the data was read.
+        pub rx_read: Cell<u32>,
+        /// Number of halves pointed to by the DMA so far.
+        pub rx_queued: Cell<u32>,
+        /// Number of `RXSTARTED` events handled.
+        pub rx_started: Cell<u32>,
+        /// Number of `ENDRX` events handled.
+        pub rx_ended: Cell<u32>,
+        pub rx_error: Cell<bool>,
+        #[cfg(feature = "embedded-io-async")]
+        pub rx_waker: AtomicWaker,
+
+        tx_ptr: AtomicPtr<u8>,
+        tx_len: AtomicUsize,
+        /// Index of the first byte to send.
+        pub tx_start: Cell<usize>,
+        /// Number of bytes waiting to be sent, including the ones being sent.
+        pub tx_count: Cell<usize>,
+        /// Number of bytes being sent.
+        pub tx_busy: Cell<usize>,
+        #[cfg(feature = "embedded-io-async")]
+        pub tx_waker: AtomicWaker,
+    }
+
+    // The `Cell`s are never accessed concurrently, see above.
+    unsafe impl Sync for State {}
+
+    impl State {
+        pub const fn new() -> Self {
+            Self {
+                rx_ptr: AtomicPtr::new(core::ptr::null_mut()),
+                rx_len: AtomicUsize::new(0),
+                rx_read: Cell::new(0),
+                rx_queued: Cell::new(0),
+                rx_started: Cell::new(0),
+                rx_ended: Cell::new(0),
+                rx_error: Cell::new(false),
+                #[cfg(feature = "embedded-io-async")]
+                rx_waker: AtomicWaker::new(),
+                tx_ptr: AtomicPtr::new(core::ptr::null_mut()),
+                tx_len: AtomicUsize::new(0),
+                tx_start: Cell::new(0),
+                tx_count: Cell::new(0),
+                tx_busy: Cell::new(0),
+                #[cfg(feature = "embedded-io-async")]
+                tx_waker: AtomicWaker::new(),
+            }
+        }
+
+        pub fn init(&self, rx_buf: &'static mut [u8], tx_buf: &'static mut [u8]) {
+            self.rx_ptr.store(rx_buf.as_mut_ptr(), Relaxed);
+            self.rx_len.store(rx_buf.len(), Relaxed);
+            self.rx_reset();
+            self.tx_ptr.store(tx_buf.as_mut_ptr(), Relaxed);
+            self.tx_len.store(tx_buf.len(), Relaxed);
+            self.tx_start.set(0);
+            self.tx_count.set(0);
+            self.tx_busy.set(0);
+        }
+
+        pub fn rx_reset(&self) {
+            self.rx_read.set(0);
+            self.rx_queued.set(0);
+            self.rx_started.set(0);
+            self.rx_ended.set(0);
+            self.rx_error.set(false);
+        }
+
+        pub fn rx_ptr(&self) -> *mut u8 {
+            self.rx_ptr.load(Relaxed)
+        }
+
+        pub fn rx_len(&self) -> usize {
+            self.rx_len.load(Relaxed)
+        }
+
+        pub fn rx_half(&self) -> u32 {
+            (self.rx_len() / 2) as u32
+        }
+
+        pub fn tx_ptr(&self) -> *mut u8 {
+            self.tx_ptr.load(Relaxed)
+        }
+
+        pub fn tx_len(&self) -> usize {
+            self.tx_len.load(Relaxed)
+        }
+    }
+}
+use sealed::State;
+
+macro_rules! impl_instance {
+    ($($uarte:ident,)*) => {
+        $(
+            impl sealed::Sealed for $uarte {
+                fn regs() -> &'static uarte0::RegisterBlock {
+                    unsafe { &*$uarte::ptr() }
+                }
+
+                fn state() -> &'static State {
+                    static STATE: State = State::new();
+                    &STATE
+                }
+            }
+            impl Instance for $uarte {}
+        )*
+    };
+}
+
+impl_instance!(UARTE0,);
+
+#[cfg(any(feature = "52833", feature = "52840"))]
+impl_instance!(UARTE1,);
@@ -0,0 +1,51 @@
+use crate::dppi::Event;
+
+// Event impls, for the peripherals supported by the HAL
+impl Event for crate::pac::timer0_ns::EVENTS_COMPARE {}
+impl Event for crate::pac::rtc0_ns::EVENTS_TICK {}
+impl Event for crate::pac::rtc0_ns::EVENTS_OVRFLW {}
+impl Event for crate::pac::rtc0_ns::EVENTS_COMPARE {}
+impl Event for crate::pac::saadc_ns::EVENTS_STARTED {}
+impl Event for crate::pac::saadc_ns::EVENTS_END {}
+impl Event for crate::pac::saadc_ns::EVENTS_DONE {}
+impl Event for crate::pac::saadc_ns::EVENTS_RESULTDONE {}
+impl Event for crate::pac::saadc_ns::EVENTS_CALIBRATEDONE {}
+impl Event for crate::pac::saadc_ns::EVENTS_STOPPED {}
+impl Event for crate::pac::pwm0_ns::EVENTS_STOPPED {}
+impl Event for crate::pac::pwm0_ns::EVENTS_SEQSTARTED {}
+impl Event for crate::pac::pwm0_ns::EVENTS_SEQEND {}
+impl Event for crate::pac::pwm0_ns::EVENTS_PWMPERIODEND {}
+impl Event for crate::pac::pwm0_ns::EVENTS_LOOPSDONE {}
+impl Event for crate::pac::uarte0_ns::EVENTS_CTS {}
+impl Event for crate::pac::uarte0_ns::EVENTS_NCTS {}
+impl Event for crate::pac::uarte0_ns::EVENTS_RXDRDY {}
+impl Event for crate::pac::uarte0_ns::EVENTS_ENDRX {}
+impl Event for crate::pac::uarte0_ns::EVENTS_TXDRDY {}
+impl Event for crate::pac::uarte0_ns::EVENTS_ENDTX {}
+impl Event for crate::pac::uarte0_ns::EVENTS_ERROR {}
+impl Event for crate::pac::uarte0_ns::EVENTS_RXTO {}
+impl Event for crate::pac::uarte0_ns::EVENTS_RXSTARTED {}
+impl Event for crate::pac::uarte0_ns::EVENTS_TXSTARTED {}
+impl Event for crate::pac::uarte0_ns::EVENTS_TXSTOPPED {}
+impl Event for crate::pac::spim0_ns::EVENTS_STOPPED {}
+impl Event for crate::pac::spim0_ns::EVENTS_ENDRX {}
+impl Event for crate::pac::spim0_ns::EVENTS_END {}
+impl Event for crate::pac::spim0_ns::EVENTS_ENDTX {}
+impl Event for crate::pac::spim0_ns::EVENTS_STARTED {}
+impl Event for crate::pac::twim0_ns::EVENTS_STOPPED {}
+impl Event for crate::pac::twim0_ns::EVENTS_ERROR {}
+impl Event for crate::pac::twim0_ns::EVENTS_SUSPENDED {}
+impl Event for crate::pac::twim0_ns::EVENTS_RXSTARTED {}
+impl Event for crate::pac::twim0_ns::EVENTS_TXSTARTED {}
+impl Event for crate::pac::twim0_ns::EVENTS_LASTRX {}
+impl Event for crate::pac::twim0_ns::EVENTS_LASTTX {}
+impl Event for crate::pac::i2s0_ns::EVENTS_RXPTRUPD {}
+impl Event for crate::pac::i2s0_ns::EVENTS_STOPPED {}
+impl Event for crate::pac::i2s0_ns::EVENTS_TXPTRUPD {}
+impl Event for crate::pac::pdm0_ns::EVENTS_STARTED {}
+impl Event for crate::pac::pdm0_ns::EVENTS_STOPPED {}
+impl Event for crate::pac::pdm0_ns::EVENTS_END {}
+impl Event for crate::pac::qspi_ns::EVENTS_READY {}
+impl Event for crate::pac::egu0_ns::EVENTS_TRIGGERED {}
+impl Event for crate::pac::wdt0_ns::EVENTS_TIMEOUT {}
+impl Event for crate::pac::ipc_ns::EVENTS_RECEIVE {}
@@ -0,0 +1,69 @@
+use crate::dppi::Event;
+
+// Event impls, for the peripherals supported by the HAL
+impl Event for crate::pac::radio_ns::EVENTS_READY {}
+impl Event for crate::pac::radio_ns::EVENTS_ADDRESS {}
+impl Event for crate::pac::radio_ns::EVENTS_PAYLOAD {}
+impl Event for crate::pac::radio_ns::EVENTS_END {}
+impl Event for crate::pac::radio_ns::EVENTS_DISABLED {}
+impl Event for crate::pac::radio_ns::EVENTS_DEVMATCH {}
+impl Event for crate::pac::radio_ns::EVENTS_DEVMISS {}
+impl Event for crate::pac::radio_ns::EVENTS_RSSIEND {}
+impl Event for crate::pac::radio_ns::EVENTS_BCMATCH {}
+impl Event for crate::pac::radio_ns::EVENTS_CRCOK {}
+impl Event for crate::pac::radio_ns::EVENTS_CRCERROR {}
+impl Event for crate::pac::radio_ns::EVENTS_FRAMESTART {}
+impl Event for crate::pac::radio_ns::EVENTS_EDEND {}
+impl Event for crate::pac::radio_ns::EVENTS_EDSTOPPED {}
+impl Event for crate::pac::radio_ns::EVENTS_CCAIDLE {}
+impl Event for crate::pac::radio_ns::EVENTS_CCABUSY {}
+impl Event for crate::pac::radio_ns::EVENTS_CCASTOPPED {}
+impl Event for crate::pac::radio_ns::EVENTS_RATEBOOST {}
+impl Event for crate::pac::radio_ns::EVENTS_TXREADY {}
+impl Event for crate::pac::radio_ns::EVENTS_RXREADY {}
+impl Event for crate::pac::radio_ns::EVENTS_MHRMATCH {}
+impl Event for crate::pac::radio_ns::EVENTS_SYNC {}
+impl Event for crate::pac::radio_ns::EVENTS_PHYEND {}
+impl Event for crate::pac::radio_ns::EVENTS_CTEPRESENT {}
+impl Event for crate::pac::timer0_ns::EVENTS_COMPARE {}
+impl Event for crate::pac::rtc0_ns::EVENTS_TICK {}
+impl Event for crate::pac::rtc0_ns::EVENTS_OVRFLW {}
+impl Event for crate::pac::rtc0_ns::EVENTS_COMPARE {}
+impl Event for crate::pac::gpiote_ns::EVENTS_IN {}
+impl Event for crate::pac::gpiote_ns::EVENTS_PORT {}
+impl Event for crate::pac::uarte0_ns::EVENTS_CTS {}
+impl Event for crate::pac::uarte0_ns::EVENTS_NCTS {}
+impl Event for crate::pac::uarte0_ns::EVENTS_RXDRDY {}
+impl Event for crate::pac::uarte0_ns::EVENTS_ENDRX {}
+impl Event for crate::pac::uarte0_ns::EVENTS_TXDRDY {}
+impl Event for crate::pac::uarte0_ns::EVENTS_ENDTX {}
+impl Event for crate::pac::uarte0_ns::EVENTS_ERROR {}
+impl Event for crate::pac::uarte0_ns::EVENTS_RXTO {}
+impl Event for crate::pac::uarte0_ns::EVENTS_RXSTARTED {}
+impl Event for crate::pac::uarte0_ns::EVENTS_TXSTARTED {}
+impl Event for crate::pac::uarte0_ns::EVENTS_TXSTOPPED {}
+impl Event for crate::pac::spim0_ns::EVENTS_STOPPED {}
+impl Event for crate::pac::spim0_ns::EVENTS_ENDRX {}
+impl Event for crate::pac::spim0_ns::EVENTS_END {}
+impl Event for crate::pac::spim0_ns::EVENTS_ENDTX {}
+impl Event for crate::pac::spim0_ns::EVENTS_STARTED {}
+impl Event for crate::pac::twim0_ns::EVENTS_STOPPED {}
+impl Event for crate::pac::twim0_ns::EVENTS_ERROR {}
+impl Event for crate::pac::twim0_ns::EVENTS_SUSPENDED {}
+impl Event for crate::pac::twim0_ns::EVENTS_RXSTARTED {}
+impl Event for crate::pac::twim0_ns::EVENTS_TXSTARTED {}
+impl Event for crate::pac::twim0_ns::EVENTS_LASTRX {}
+impl Event for crate::pac::twim0_ns::EVENTS_LASTTX {}
+impl Event for crate::pac::egu0_ns::EVENTS_TRIGGERED {}
+impl Event for crate::pac::wdt_ns::EVENTS_TIMEOUT {}
+impl Event for crate::pac::ipc_ns::EVENTS_RECEIVE {}
+impl Event for crate::pac::rng_ns::EVENTS_VALRDY {}
+impl Event for crate::pac::temp_ns::EVENTS_DATARDY {}
+impl Event for crate::pac::ecb_ns::EVENTS_ENDECB {}
+impl Event for crate::pac::ecb_ns::EVENTS_ERRORECB {}
+impl Event for crate::pac::ccm_ns::EVENTS_ENDKSGEN {}
+impl Event for crate::pac::ccm_ns::EVENTS_ENDCRYPT {}
+impl Event for crate::pac::ccm_ns::EVENTS_ERROR {}
+impl Event for crate::pac::aar_ns::EVENTS_END {}
+impl Event for crate::pac::aar_ns::EVENTS_RESOLVED {}
+impl Event for crate::pac::aar_ns::EVENTS_NOTRESOLVED {}
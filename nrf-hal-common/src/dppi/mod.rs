@@ -0,0 +1,314 @@
+//! HAL interface for the DPPI peripheral.
+//!
+//! The Distributed Programmable Peripheral Interconnect interface allows autonomous interaction
+//! between peripherals through their events and tasks. Unlike PPI, an event is not connected to a
+//! task directly: events *publish* on a channel, and tasks *subscribe* to a channel. Any number of
+//! events can publish on, and any number of tasks can subscribe to, the same channel. A channel
+//! only propagates the events once it is enabled.
+//!
+//! Channels can be connected with [`Dppi::publish`] and [`Dppi::subscribe`], or with the
+//! [`Publish`] and [`Subscribe`] helpers on the event and task register types:
+//!
+//! ```ignore
+//! let mut dppi = dppi::Parts::new(board.DPPIC_NS);
+//! timer.event_compare_cc0().publish(&dppi.dppi0);
+//! saadc_task_sample.subscribe(&dppi.dppi0);
+//! dppi.dppi0.enable();
+//! ```
+
+use crate::pac::dppic_ns::tasks_chg::{DIS, EN};
+use crate::pac::DPPIC_NS as DPPIC;
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(feature = "9160")] {
+        mod event_nrf9160;
+        mod task_nrf9160;
+    } else if #[cfg(feature = "5340-app")] {
+        mod event_nrf5340_app;
+        mod task_nrf5340_app;
+    } else if #[cfg(feature = "5340-net")] {
+        mod event_nrf5340_net;
+        mod task_nrf5340_net;
+    }
+}
+
+/// Offset of the `SUBSCRIBE_*` register of a task, and of the `PUBLISH_*` register of an event.
+const CONFIG_OFFSET: u32 = 0x80;
+
+/// Enables publishing or subscribing in a `PUBLISH_*` or `SUBSCRIBE_*` register.
+const CONFIG_EN: u32 = 1 << 31;
+
+mod sealed {
+    pub trait Channel {
+        const CH: usize;
+    }
+
+    pub trait Task {
+        #[inline(always)]
+        fn task_addr(&self) -> u32 {
+            self as *const _ as *const u32 as u32
+        }
+    }
+
+    pub trait Event {
+        #[inline(always)]
+        fn event_addr(&self) -> u32 {
+            self as *const _ as *const u32 as u32
+        }
+    }
+
+    pub trait ChannelGroup {
+        const CHG: usize;
+    }
+
+    pub trait DppiSealed {}
+    pub trait DppiChannelGroupSealed {}
+}
+use sealed::{Channel, ChannelGroup, DppiChannelGroupSealed, DppiSealed, Event, Task};
+
+/// Trait to represent a Distributed Programmable Peripheral Interconnect channel.
+pub trait Dppi: DppiSealed {
+    /// Returns the index of the channel.
+    fn channel(&self) -> usize;
+
+    /// Enables the channel.
+    fn enable(&mut self);
+
+    /// Disables the channel.
+    fn disable(&mut self);
+
+    /// Publishes the event on this channel. The user must provide a reference to the event.
+    fn publish<E: Publish>(&mut self, event: &E) {
+        event.publish(self);
+    }
+
+    /// Subscribes the task to this channel. The user must provide a reference to the task.
+    fn subscribe<T: Subscribe>(&mut self, task: &T) {
+        task.subscribe(self);
+    }
+}
+
+/// Publish/unpublish helpers, implemented by all event registers that can publish on a DPPI
+/// channel.
+pub trait Publish: Event {
+    /// Publishes this event on `channel`, replacing any previous channel.
+    fn publish<C: Dppi + ?Sized>(&self, channel: &C) {
+        let config = (self.event_addr() + CONFIG_OFFSET) as *mut u32;
+        // NB Safety: the `PUBLISH_*` register of an event is always located at the same offset.
+        unsafe { config.write_volatile(CONFIG_EN | channel.channel() as u32) };
+    }
+
+    /// Stops publishing this event.
+    fn unpublish(&self) {
+        let config = (self.event_addr() + CONFIG_OFFSET) as *mut u32;
+        // NB Safety: the `PUBLISH_*` register of an event is always located at the same offset.
+        unsafe { config.write_volatile(0) };
+    }
+}
+impl<E: Event> Publish for E {}
+
+/// Subscribe/unsubscribe helpers, implemented by all task registers that can subscribe to a DPPI
+/// channel.
+pub trait Subscribe: Task {
+    /// Subscribes this task to `channel`, replacing any previous channel.
+    fn subscribe<C: Dppi + ?Sized>(&self, channel: &C) {
+        let config = (self.task_addr() + CONFIG_OFFSET) as *mut u32;
+        // NB Safety: the `SUBSCRIBE_*` register of a task is always located at the same offset.
+        unsafe { config.write_volatile(CONFIG_EN | channel.channel() as u32) };
+    }
+
+    /// Stops subscribing this task.
+    fn unsubscribe(&self) {
+        let config = (self.task_addr() + CONFIG_OFFSET) as *mut u32;
+        // NB Safety: the `SUBSCRIBE_*` register of a task is always located at the same offset.
+        unsafe { config.write_volatile(0) };
+    }
+}
+impl<T: Task> Subscribe for T {}
+
+/// Trait for a DPPI channel group.
+pub trait DppiChannelGroup: DppiChannelGroupSealed {
+    /// Returns reference to `tasks_chg[x].en` endpoint for enabling channel group.
+    fn task_enable(&self) -> &EN;
+    /// Returns reference to `tasks_chg[x].dis` endpoint for disabling channel group.
+    fn task_disable(&self) -> &DIS;
+    /// Sets bitmask for DPPI channels which shall be included in this channel group.
+    fn set_channels(&self, mask: u32);
+    /// Enables this channel group.
+    fn enable(&self);
+    /// Disables this channel group.
+    fn disable(&self);
+}
+
+// All unsafe `ptr` calls only uses registers atomically, and only changes the resources owned by
+// the type (guaranteed by the abstraction).
+impl<C: Channel> DppiSealed for C {}
+impl<C: Channel> Dppi for C {
+    #[inline(always)]
+    fn channel(&self) -> usize {
+        C::CH
+    }
+
+    #[inline(always)]
+    fn enable(&mut self) {
+        let regs = unsafe { &*DPPIC::ptr() };
+        regs.chenset.write(|w| unsafe { w.bits(1 << C::CH) });
+    }
+
+    #[inline(always)]
+    fn disable(&mut self) {
+        let regs = unsafe { &*DPPIC::ptr() };
+        regs.chenclr.write(|w| unsafe { w.bits(1 << C::CH) });
+    }
+}
+
+impl<G: ChannelGroup> DppiChannelGroupSealed for G {}
+impl<G: ChannelGroup> DppiChannelGroup for G {
+    #[inline(always)]
+    fn task_enable(&self) -> &EN {
+        let regs = unsafe { &*DPPIC::ptr() };
+        &regs.tasks_chg[Self::CHG].en
+    }
+    #[inline(always)]
+    fn task_disable(&self) -> &DIS {
+        let regs = unsafe { &*DPPIC::ptr() };
+        &regs.tasks_chg[Self::CHG].dis
+    }
+    #[inline(always)]
+    fn set_channels(&self, mask: u32) {
+        let regs = unsafe { &*DPPIC::ptr() };
+        regs.chg[Self::CHG].write(|w| unsafe { w.bits(mask) });
+    }
+    #[inline(always)]
+    fn enable(&self) {
+        self.task_enable().write(|w| unsafe { w.bits(1) });
+    }
+    #[inline(always)]
+    fn disable(&self) {
+        self.task_disable().write(|w| unsafe { w.bits(1) });
+    }
+}
+
+macro_rules! dppi {
+    (
+        channels: [$($(#[$cattr:meta])* ($dppix:ident, $DppixType:ident, $ch:expr),)+],
+        groups: [$(($chgx:ident, $ChgxType:ident, $chg:expr),)+],
+    ) => {
+        $(
+            /// DPPI channel.
+            $(#[$cattr])*
+            pub struct $DppixType {
+                _private: (),
+            }
+
+            $(#[$cattr])*
+            impl Channel for $DppixType {
+                const CH: usize = $ch;
+            }
+        )+
+
+        $(
+            /// Channel groups.
+            pub struct $ChgxType {
+                _private: (),
+            }
+
+            impl ChannelGroup for $ChgxType {
+                const CHG: usize = $chg;
+            }
+        )+
+
+        /// Type that abstracts all the DPPI channels.
+        pub struct Parts {
+            $(
+                $(#[$cattr])*
+                pub $dppix: $DppixType,
+            )+
+            $(
+                pub $chgx: $ChgxType,
+            )+
+        }
+
+        impl Parts {
+            /// Gets access to the DPPI abstraction, making it possible to separate the channels
+            /// through different objects.
+            pub fn new(_regs: DPPIC) -> Self {
+                Self {
+                    $(
+                        $(#[$cattr])*
+                        $dppix: $DppixType {
+                            _private: (),
+                        },
+                    )+
+                    $(
+                        $chgx: $ChgxType {
+                            _private: (),
+                        },
+                    )+
+                }
+            }
+        }
+    };
+}
+
+dppi!(
+    channels: [
+        (dppi0, Dppi0, 0),
+        (dppi1, Dppi1, 1),
+        (dppi2, Dppi2, 2),
+        (dppi3, Dppi3, 3),
+        (dppi4, Dppi4, 4),
+        (dppi5, Dppi5, 5),
+        (dppi6, Dppi6, 6),
+        (dppi7, Dppi7, 7),
+        (dppi8, Dppi8, 8),
+        (dppi9, Dppi9, 9),
+        (dppi10, Dppi10, 10),
+        (dppi11, Dppi11, 11),
+        (dppi12, Dppi12, 12),
+        (dppi13, Dppi13, 13),
+        (dppi14, Dppi14, 14),
+        (dppi15, Dppi15, 15),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi16, Dppi16, 16),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi17, Dppi17, 17),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi18, Dppi18, 18),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi19, Dppi19, 19),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi20, Dppi20, 20),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi21, Dppi21, 21),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi22, Dppi22, 22),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi23, Dppi23, 23),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi24, Dppi24, 24),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi25, Dppi25, 25),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi26, Dppi26, 26),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi27, Dppi27, 27),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi28, Dppi28, 28),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi29, Dppi29, 29),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi30, Dppi30, 30),
+        #[cfg(any(feature = "5340-app", feature = "5340-net"))]
+        (dppi31, Dppi31, 31),
+    ],
+    groups: [
+        (chg0, Chg0, 0),
+        (chg1, Chg1, 1),
+        (chg2, Chg2, 2),
+        (chg3, Chg3, 3),
+        (chg4, Chg4, 4),
+        (chg5, Chg5, 5),
+    ],
+);
@@ -0,0 +1,59 @@
+use crate::dppi::Task;
+
+// Task impls, for the peripherals supported by the HAL
+impl Task for crate::pac::radio_ns::TASKS_TXEN {}
+impl Task for crate::pac::radio_ns::TASKS_RXEN {}
+impl Task for crate::pac::radio_ns::TASKS_START {}
+impl Task for crate::pac::radio_ns::TASKS_STOP {}
+impl Task for crate::pac::radio_ns::TASKS_DISABLE {}
+impl Task for crate::pac::radio_ns::TASKS_RSSISTART {}
+impl Task for crate::pac::radio_ns::TASKS_RSSISTOP {}
+impl Task for crate::pac::radio_ns::TASKS_BCSTART {}
+impl Task for crate::pac::radio_ns::TASKS_BCSTOP {}
+impl Task for crate::pac::radio_ns::TASKS_EDSTART {}
+impl Task for crate::pac::radio_ns::TASKS_EDSTOP {}
+impl Task for crate::pac::radio_ns::TASKS_CCASTART {}
+impl Task for crate::pac::radio_ns::TASKS_CCASTOP {}
+impl Task for crate::pac::timer0_ns::TASKS_START {}
+impl Task for crate::pac::timer0_ns::TASKS_STOP {}
+impl Task for crate::pac::timer0_ns::TASKS_COUNT {}
+impl Task for crate::pac::timer0_ns::TASKS_CLEAR {}
+impl Task for crate::pac::timer0_ns::TASKS_CAPTURE {}
+impl Task for crate::pac::rtc0_ns::TASKS_START {}
+impl Task for crate::pac::rtc0_ns::TASKS_STOP {}
+impl Task for crate::pac::rtc0_ns::TASKS_CLEAR {}
+impl Task for crate::pac::rtc0_ns::TASKS_TRIGOVRFLW {}
+impl Task for crate::pac::gpiote_ns::TASKS_OUT {}
+impl Task for crate::pac::gpiote_ns::TASKS_SET {}
+impl Task for crate::pac::gpiote_ns::TASKS_CLR {}
+impl Task for crate::pac::uarte0_ns::TASKS_STARTRX {}
+impl Task for crate::pac::uarte0_ns::TASKS_STOPRX {}
+impl Task for crate::pac::uarte0_ns::TASKS_STARTTX {}
+impl Task for crate::pac::uarte0_ns::TASKS_STOPTX {}
+impl Task for crate::pac::uarte0_ns::TASKS_FLUSHRX {}
+impl Task for crate::pac::spim0_ns::TASKS_START {}
+impl Task for crate::pac::spim0_ns::TASKS_STOP {}
+impl Task for crate::pac::spim0_ns::TASKS_SUSPEND {}
+impl Task for crate::pac::spim0_ns::TASKS_RESUME {}
+impl Task for crate::pac::twim0_ns::TASKS_STARTRX {}
+impl Task for crate::pac::twim0_ns::TASKS_STARTTX {}
+impl Task for crate::pac::twim0_ns::TASKS_STOP {}
+impl Task for crate::pac::twim0_ns::TASKS_SUSPEND {}
+impl Task for crate::pac::twim0_ns::TASKS_RESUME {}
+impl Task for crate::pac::egu0_ns::TASKS_TRIGGER {}
+impl Task for crate::pac::wdt_ns::TASKS_START {}
+impl Task for crate::pac::ipc_ns::TASKS_SEND {}
+impl Task for crate::pac::rng_ns::TASKS_START {}
+impl Task for crate::pac::rng_ns::TASKS_STOP {}
+impl Task for crate::pac::temp_ns::TASKS_START {}
+impl Task for crate::pac::temp_ns::TASKS_STOP {}
+impl Task for crate::pac::ecb_ns::TASKS_STARTECB {}
+impl Task for crate::pac::ecb_ns::TASKS_STOPECB {}
+impl Task for crate::pac::ccm_ns::TASKS_KSGEN {}
+impl Task for crate::pac::ccm_ns::TASKS_CRYPT {}
+impl Task for crate::pac::ccm_ns::TASKS_STOP {}
+impl Task for crate::pac::ccm_ns::TASKS_RATEOVERRIDE {}
+impl Task for crate::pac::aar_ns::TASKS_START {}
+impl Task for crate::pac::aar_ns::TASKS_STOP {}
+impl Task for crate::pac::dppic_ns::tasks_chg::EN {}
+impl Task for crate::pac::dppic_ns::tasks_chg::DIS {}
@@ -0,0 +1,45 @@
+use crate::dppi::Task;
+
+// Task impls, for the peripherals supported by the HAL
+impl Task for crate::pac::timer0_ns::TASKS_START {}
+impl Task for crate::pac::timer0_ns::TASKS_STOP {}
+impl Task for crate::pac::timer0_ns::TASKS_COUNT {}
+impl Task for crate::pac::timer0_ns::TASKS_CLEAR {}
+impl Task for crate::pac::timer0_ns::TASKS_CAPTURE {}
+impl Task for crate::pac::rtc0_ns::TASKS_START {}
+impl Task for crate::pac::rtc0_ns::TASKS_STOP {}
+impl Task for crate::pac::rtc0_ns::TASKS_CLEAR {}
+impl Task for crate::pac::rtc0_ns::TASKS_TRIGOVRFLW {}
+impl Task for crate::pac::gpiote0_s::TASKS_OUT {}
+impl Task for crate::pac::gpiote0_s::TASKS_SET {}
+impl Task for crate::pac::gpiote0_s::TASKS_CLR {}
+impl Task for crate::pac::saadc_ns::TASKS_START {}
+impl Task for crate::pac::saadc_ns::TASKS_SAMPLE {}
+impl Task for crate::pac::saadc_ns::TASKS_STOP {}
+impl Task for crate::pac::saadc_ns::TASKS_CALIBRATEOFFSET {}
+impl Task for crate::pac::pwm0_ns::TASKS_STOP {}
+impl Task for crate::pac::pwm0_ns::TASKS_SEQSTART {}
+impl Task for crate::pac::pwm0_ns::TASKS_NEXTSTEP {}
+impl Task for crate::pac::uarte0_ns::TASKS_STARTRX {}
+impl Task for crate::pac::uarte0_ns::TASKS_STOPRX {}
+impl Task for crate::pac::uarte0_ns::TASKS_STARTTX {}
+impl Task for crate::pac::uarte0_ns::TASKS_STOPTX {}
+impl Task for crate::pac::uarte0_ns::TASKS_FLUSHRX {}
+impl Task for crate::pac::spim0_ns::TASKS_START {}
+impl Task for crate::pac::spim0_ns::TASKS_STOP {}
+impl Task for crate::pac::spim0_ns::TASKS_SUSPEND {}
+impl Task for crate::pac::spim0_ns::TASKS_RESUME {}
+impl Task for crate::pac::twim0_ns::TASKS_STARTRX {}
+impl Task for crate::pac::twim0_ns::TASKS_STARTTX {}
+impl Task for crate::pac::twim0_ns::TASKS_STOP {}
+impl Task for crate::pac::twim0_ns::TASKS_SUSPEND {}
+impl Task for crate::pac::twim0_ns::TASKS_RESUME {}
+impl Task for crate::pac::i2s_ns::TASKS_START {}
+impl Task for crate::pac::i2s_ns::TASKS_STOP {}
+impl Task for crate::pac::pdm_ns::TASKS_START {}
+impl Task for crate::pac::pdm_ns::TASKS_STOP {}
+impl Task for crate::pac::egu0_ns::TASKS_TRIGGER {}
+impl Task for crate::pac::wdt_ns::TASKS_START {}
+impl Task for crate::pac::ipc_ns::TASKS_SEND {}
+impl Task for crate::pac::dppic_ns::tasks_chg::EN {}
+impl Task for crate::pac::dppic_ns::tasks_chg::DIS {}
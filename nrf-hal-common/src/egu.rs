@@ -0,0 +1,173 @@
+//! HAL interface to the Event Generator Unit (EGU) peripheral.
+//!
+//! The EGU provides support for triggering events from software, and for generating interrupts
+//! from PPI chains. Triggering the `TRIGGER[n]` task, from software or through PPI, generates the
+//! `TRIGGERED[n]` event, which can in turn trigger other tasks through PPI and fire the interrupt
+//! of the EGU instance.
+
+use core::ops::Deref;
+
+#[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+use crate::pac::{
+    egu0_ns::{self as egu0, EVENTS_TRIGGERED, TASKS_TRIGGER},
+    Interrupt, EGU0_NS as EGU0, NVIC,
+};
+
+#[cfg(any(feature = "9160", feature = "5340-app"))]
+use crate::pac::{
+    EGU1_NS as EGU1, EGU2_NS as EGU2, EGU3_NS as EGU3, EGU4_NS as EGU4, EGU5_NS as EGU5,
+};
+
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+use crate::pac::{
+    egu0::{self, EVENTS_TRIGGERED, TASKS_TRIGGER},
+    Interrupt, EGU0, EGU1, NVIC,
+};
+
+#[cfg(any(feature = "52832", feature = "52833", feature = "52840"))]
+use crate::pac::{EGU2, EGU3, EGU4, EGU5};
+
+/// Number of channels of an EGU instance.
+pub const CHANNELS: usize = 16;
+
+/// Interface to an EGU instance.
+pub struct Egu<T>(T);
+
+impl<T> Egu<T>
+where
+    T: Instance,
+{
+    /// Takes ownership of the EGU instance, disabling all its interrupts and clearing all its
+    /// events.
+    pub fn new(egu: T) -> Self {
+        egu.intenclr.write(|w| unsafe { w.bits(0xffff) });
+        for event in egu.events_triggered.iter() {
+            event.reset();
+        }
+        Egu(egu)
+    }
+
+    /// Triggers the `TRIGGER[channel]` task, which generates the `TRIGGERED[channel]` event.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is not less than [`CHANNELS`].
+    pub fn trigger(&self, channel: usize) {
+        self.0.tasks_trigger[channel].write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Checks if the `TRIGGERED[channel]` event has been generated.
+    pub fn is_triggered(&self, channel: usize) -> bool {
+        self.0.events_triggered[channel].read().bits() != 0
+    }
+
+    /// Resets the `TRIGGERED[channel]` event.
+    pub fn reset_event(&self, channel: usize) {
+        self.0.events_triggered[channel].reset();
+    }
+
+    /// Enables the interrupt for the `TRIGGERED[channel]` event.
+    ///
+    /// If access to the NVIC is not provided, the interrupt must ALSO be enabled
+    /// there outside of this function (e.g. manually call `nvic.enable`, or through
+    /// the use of RTIC).
+    pub fn enable_interrupt(&mut self, channel: usize, nvic: Option<&mut NVIC>) {
+        assert!(channel < CHANNELS);
+        self.0.intenset.write(|w| unsafe { w.bits(1 << channel) });
+        if let Some(_nvic) = nvic {
+            unsafe { NVIC::unmask(T::INTERRUPT) };
+        }
+    }
+
+    /// Disables the interrupt for the `TRIGGERED[channel]` event.
+    ///
+    /// If access to the NVIC is not provided, the interrupt must ALSO be disabled
+    /// there outside of this function (e.g. manually call `nvic.disable`, or through
+    /// the use of RTIC).
+    pub fn disable_interrupt(&mut self, channel: usize, nvic: Option<&mut NVIC>) {
+        assert!(channel < CHANNELS);
+        self.0.intenclr.write(|w| unsafe { w.bits(1 << channel) });
+        if let Some(_nvic) = nvic {
+            NVIC::mask(T::INTERRUPT);
+        }
+    }
+
+    /// Returns the channels whose `TRIGGERED` event has been generated, as a bit mask, and resets
+    /// these events.
+    ///
+    /// This is meant to be called from the interrupt handler of the EGU instance.
+    pub fn take_triggered(&self) -> u16 {
+        let mut triggered = 0;
+        for (channel, event) in self.0.events_triggered.iter().enumerate() {
+            if event.read().bits() != 0 {
+                event.reset();
+                triggered |= 1 << channel;
+            }
+        }
+        triggered
+    }
+
+    /// Returns reference to the `TRIGGER[channel]` task endpoint for PPI.
+    #[inline(always)]
+    pub fn task_trigger(&self, channel: usize) -> &TASKS_TRIGGER {
+        &self.0.tasks_trigger[channel]
+    }
+
+    /// Returns reference to the `TRIGGERED[channel]` event endpoint for PPI.
+    #[inline(always)]
+    pub fn event_triggered(&self, channel: usize) -> &EVENTS_TRIGGERED {
+        &self.0.events_triggered[channel]
+    }
+
+    /// Releases the EGU instance. Does not reset the interrupts enabled with `enable_interrupt`.
+    pub fn free(self) -> T {
+        self.0
+    }
+}
+
+/// Implemented by all EGU instances.
+pub trait Instance: Deref<Target = egu0::RegisterBlock> + sealed::Sealed {
+    /// The interrupt associated with this EGU instance.
+    const INTERRUPT: Interrupt;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_instance {
+    ($($name:ident => $interrupt:ident,)*) => {
+        $(
+            impl Instance for $name {
+                const INTERRUPT: Interrupt = Interrupt::$interrupt;
+            }
+            impl sealed::Sealed for $name {}
+        )*
+    }
+}
+
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+impl_instance!(
+    EGU0 => SWI0_EGU0,
+    EGU1 => SWI1_EGU1,
+);
+
+#[cfg(any(feature = "52832", feature = "52833", feature = "52840"))]
+impl_instance!(
+    EGU2 => SWI2_EGU2,
+    EGU3 => SWI3_EGU3,
+    EGU4 => SWI4_EGU4,
+    EGU5 => SWI5_EGU5,
+);
+
+#[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+impl_instance!(EGU0 => EGU0,);
+
+#[cfg(any(feature = "9160", feature = "5340-app"))]
+impl_instance!(
+    EGU1 => EGU1,
+    EGU2 => EGU2,
+    EGU3 => EGU3,
+    EGU4 => EGU4,
+    EGU5 => EGU5,
+);
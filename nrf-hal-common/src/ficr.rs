@@ -0,0 +1,268 @@
+//! HAL interface to the Factory Information Configuration Registers (FICR).
+//!
+//! The FICR are pre-programmed in the factory and contain the device identity and information
+//! about the chip, like its part number, package and memory sizes. They are read-only.
+
+use cfg_if::cfg_if;
+
+cfg_if! {
+    if #[cfg(any(feature = "9160", feature = "5340-app"))] {
+        use crate::pac::FICR_S as FICR;
+    } else if #[cfg(feature = "5340-net")] {
+        use crate::pac::FICR_NS as FICR;
+    } else {
+        use crate::pac::FICR;
+    }
+}
+
+/// Interface to the FICR.
+pub struct Ficr(FICR);
+
+impl Ficr {
+    /// Construct a new `Ficr` from `pac::FICR`.
+    pub fn new(ficr: FICR) -> Self {
+        Self(ficr)
+    }
+
+    /// Release the `pac::FICR` instance back.
+    pub fn free(self) -> FICR {
+        self.0
+    }
+
+    /// Returns the 64 bit unique device identifier.
+    pub fn device_id(&self) -> u64 {
+        #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+        let deviceid = &self.0.info.deviceid;
+        #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+        let deviceid = &self.0.deviceid;
+
+        deviceid[0].read().bits() as u64 | (deviceid[1].read().bits() as u64) << 32
+    }
+
+    /// Returns the size of a code memory page in bytes.
+    pub fn code_page_size(&self) -> u32 {
+        #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+        {
+            self.0.info.codepagesize.read().bits()
+        }
+
+        #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+        {
+            self.0.codepagesize.read().bits()
+        }
+    }
+
+    /// Returns the size of the code memory in pages.
+    pub fn code_size(&self) -> u32 {
+        #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+        {
+            self.0.info.codesize.read().bits()
+        }
+
+        #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+        {
+            self.0.codesize.read().bits()
+        }
+    }
+
+    /// Returns the factory programmed device address, used as the default BLE address.
+    #[cfg(not(any(feature = "9160", feature = "5340-app")))]
+    pub fn device_address(&self) -> DeviceAddress {
+        let low = self.0.deviceaddr[0].read().bits();
+        let high = self.0.deviceaddr[1].read().bits();
+        let mut address = [0; 6];
+        address[..4].copy_from_slice(&low.to_le_bytes());
+        address[4..].copy_from_slice(&high.to_le_bytes()[..2]);
+
+        DeviceAddress {
+            address,
+            kind: if self.0.deviceaddrtype.read().bits() & 1 == 0 {
+                AddressType::Public
+            } else {
+                AddressType::Random
+            },
+        }
+    }
+
+    /// Returns the 128 bit Identity Root key, used to derive the BLE Identity Resolving Key.
+    #[cfg(not(any(feature = "9160", feature = "5340-app")))]
+    pub fn identity_root(&self) -> [u8; 16] {
+        let mut key = [0; 16];
+        for (chunk, word) in key.chunks_exact_mut(4).zip(self.0.ir.iter()) {
+            chunk.copy_from_slice(&word.read().bits().to_le_bytes());
+        }
+        key
+    }
+
+    /// Returns the 128 bit Encryption Root key, used to derive BLE encryption keys.
+    #[cfg(not(any(feature = "9160", feature = "5340-app")))]
+    pub fn encryption_root(&self) -> [u8; 16] {
+        let mut key = [0; 16];
+        for (chunk, word) in key.chunks_exact_mut(4).zip(self.0.er.iter()) {
+            chunk.copy_from_slice(&word.read().bits().to_le_bytes());
+        }
+        key
+    }
+
+    /// Returns the part code of the chip.
+    #[cfg(not(feature = "51"))]
+    pub fn part(&self) -> Part {
+        Part::from_bits(self.0.info.part.read().bits())
+    }
+
+    /// Returns the build code of the chip, like `AAA0`.
+    #[cfg(not(feature = "51"))]
+    pub fn variant(&self) -> Variant {
+        Variant::from_bits(self.0.info.variant.read().bits())
+    }
+
+    /// Returns the package code of the chip.
+    #[cfg(not(feature = "51"))]
+    pub fn package(&self) -> Package {
+        Package(self.0.info.package.read().bits())
+    }
+
+    /// Returns the size of the RAM in KiB.
+    #[cfg(not(feature = "51"))]
+    pub fn ram_kib(&self) -> u32 {
+        self.0.info.ram.read().bits()
+    }
+
+    /// Returns the size of the flash in KiB.
+    #[cfg(not(feature = "51"))]
+    pub fn flash_kib(&self) -> u32 {
+        self.0.info.flash.read().bits()
+    }
+}
+
+/// Type of a [`DeviceAddress`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AddressType {
+    /// IEEE public address.
+    Public,
+    /// Random static address.
+    Random,
+}
+
+/// A 48 bit device address.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceAddress {
+    /// Address bytes, least significant byte first.
+    pub address: [u8; 6],
+    /// Type of the address.
+    pub kind: AddressType,
+}
+
+/// Part code, as stored in `INFO.PART`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Part {
+    N52805,
+    N52810,
+    N52811,
+    N52820,
+    N52832,
+    N52833,
+    N52840,
+    N5340,
+    N9160,
+    /// The part code is not programmed.
+    Unspecified,
+    /// A part code not known to this crate.
+    Unknown(u32),
+}
+
+impl Part {
+    fn from_bits(bits: u32) -> Self {
+        match bits {
+            0x52805 => Part::N52805,
+            0x52810 => Part::N52810,
+            0x52811 => Part::N52811,
+            0x52820 => Part::N52820,
+            0x52832 => Part::N52832,
+            0x52833 => Part::N52833,
+            0x52840 => Part::N52840,
+            0x5340 => Part::N5340,
+            0x9160 => Part::N9160,
+            0xffff_ffff => Part::Unspecified,
+            bits => Part::Unknown(bits),
+        }
+    }
+}
+
+/// Build code (hardware version and production configuration), as stored in `INFO.VARIANT`.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum Variant {
+    /// A build code made of 4 ASCII characters, like `*b"AAA0"`.
+    Code([u8; 4]),
+    /// The build code is not programmed.
+    Unspecified,
+    /// A register value that is not a build code.
+    Unknown(u32),
+}
+
+impl Variant {
+    fn from_bits(bits: u32) -> Self {
+        let bytes = bits.to_be_bytes();
+        match bits {
+            0xffff_ffff => Variant::Unspecified,
+            _ if bytes.iter().all(u8::is_ascii_alphanumeric) => Variant::Code(bytes),
+            bits => Variant::Unknown(bits),
+        }
+    }
+
+    /// Returns the raw register value.
+    pub fn bits(&self) -> u32 {
+        match self {
+            Variant::Code(bytes) => u32::from_be_bytes(*bytes),
+            Variant::Unspecified => 0xffff_ffff,
+            Variant::Unknown(bits) => *bits,
+        }
+    }
+
+    /// Returns the 4 bytes of the register, in the order of the build code characters.
+    pub fn to_bytes(&self) -> [u8; 4] {
+        self.bits().to_be_bytes()
+    }
+}
+
+impl core::fmt::Debug for Variant {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            // Only ASCII alphanumeric characters, so the conversion can't fail.
+            Variant::Code(bytes) => f
+                .debug_tuple("Code")
+                .field(&core::str::from_utf8(bytes).unwrap_or_default())
+                .finish(),
+            Variant::Unspecified => f.write_str("Unspecified"),
+            Variant::Unknown(bits) => write!(f, "Unknown({:#010x})", bits),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Variant {
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            Variant::Code(bytes) => defmt::write!(f, "Code(\"{=[u8]:a}\")", &bytes[..]),
+            Variant::Unspecified => defmt::write!(f, "Unspecified"),
+            Variant::Unknown(bits) => defmt::write!(f, "Unknown({=u32:#010x})", bits),
+        }
+    }
+}
+
+/// Package option, as stored in `INFO.PACKAGE`.
+///
+/// The meaning of the code is chip specific, see the product specification.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Package(u32);
+
+impl Package {
+    /// Returns the raw register value.
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+}
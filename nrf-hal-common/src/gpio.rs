@@ -35,8 +35,42 @@ pub enum Level {
     High,
 }
 
+/// Pull resistor configuration of a [`Flex`] pin.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Pull {
+    /// No pull resistor.
+    None,
+    /// Pull-up resistor.
+    Up,
+    /// Pull-down resistor.
+    Down,
+}
+
+/// Pin sense level, used to generate the DETECT signal.
+///
+/// The DETECT signal drives the GPIOTE PORT event and wakes the chip up from System OFF.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Sense {
+    /// Sensing disabled.
+    Disabled,
+    /// Sense for a high level.
+    High,
+    /// Sense for a low level.
+    Low,
+}
+
+/// Behaviour of the DETECT signal of a port.
+#[cfg(any(feature = "52833", feature = "52840"))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DetectMode {
+    /// DETECT is directly connected to the PIN DETECT signals.
+    Default,
+    /// DETECT is generated from the latched PIN DETECT signals, see [`Port::latch`].
+    Latched,
+}
+
 /// A GPIO port with up to 32 pins.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Port {
     /// Port 0, available on all nRF52 and nRF51 MCUs.
     Port0,
@@ -87,6 +121,157 @@ use crate::pac::P1_NS as P1;
 
 use embedded_hal::digital::{ErrorType, InputPin, OutputPin, StatefulOutputPin};
 
+impl Port {
+    fn block(&self) -> &'static gpio::RegisterBlock {
+        let ptr = match self {
+            Port::Port0 => P0::ptr(),
+            #[cfg(feature = "5340-app")]
+            Port::Port0Secure => P0_S::ptr(),
+            #[cfg(any(feature = "52833", feature = "52840", feature = "5340-net"))]
+            Port::Port1 => P1::ptr(),
+        };
+
+        unsafe { &*ptr }
+    }
+
+    /// Returns the `LATCH` register of the port.
+    ///
+    /// A bit is set for every pin whose sense condition has been met since it was last cleared,
+    /// which makes it possible to find out which pins caused a PORT event or a wake-up from
+    /// System OFF.
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    pub fn latch(&self) -> u32 {
+        self.block().latch.read().bits()
+    }
+
+    /// Clears the `LATCH` bits set in `mask`.
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    pub fn clear_latch(&self, mask: u32) {
+        self.block().latch.write(|w| unsafe { w.bits(mask) });
+    }
+
+    /// Selects how the DETECT signal of the port is generated.
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    pub fn set_detect_mode(&self, mode: DetectMode) {
+        self.block().detectmode.write(|w| match mode {
+            DetectMode::Default => w.detectmode().default(),
+            DetectMode::Latched => w.detectmode().ldetect(),
+        });
+    }
+
+    /// Returns the number of pins of the port.
+    fn pin_count(&self) -> usize {
+        // The nRF52833 only has P1.00 to P1.09
+        #[cfg(feature = "52833")]
+        if *self == Port::Port1 {
+            return 10;
+        }
+        #[cfg(any(feature = "52840", feature = "5340-net"))]
+        if *self == Port::Port1 {
+            return 16;
+        }
+        32
+    }
+
+    /// Saves the `PIN_CNF` registers of the port, along with its `OUT` register.
+    pub fn snapshot(&self) -> PortSnapshot {
+        let block = self.block();
+        let mut pin_cnf = [0; 32];
+        for (cnf, reg) in pin_cnf.iter_mut().zip(&block.pin_cnf[..self.pin_count()]) {
+            *cnf = reg.read().bits();
+        }
+
+        PortSnapshot {
+            port: *self,
+            out: block.out.read().bits(),
+            pin_cnf,
+        }
+    }
+
+    /// Restores the registers saved by [`Port::snapshot`].
+    ///
+    /// `OUT` is restored first, so that outputs are driven at their saved level as soon as they
+    /// are configured. This overrides the configuration of every pin of the port, including the
+    /// pins owned by a [`Pin`] or a driver.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the snapshot was taken on another port.
+    pub fn restore(&self, snapshot: &PortSnapshot) {
+        assert!(
+            snapshot.port == *self,
+            "the snapshot was taken on another port"
+        );
+
+        let block = self.block();
+        block.out.write(|w| unsafe { w.bits(snapshot.out) });
+        for (cnf, reg) in snapshot
+            .pin_cnf
+            .iter()
+            .zip(&block.pin_cnf[..self.pin_count()])
+        {
+            reg.write(|w| unsafe { w.bits(*cnf) });
+        }
+    }
+
+    /// Returns the mask of the pins that are not disconnected: configured as an output, with
+    /// their input buffer connected or with a pull resistor enabled.
+    ///
+    /// These pins may draw current, in System ON as well as in System OFF.
+    pub fn active_pins(&self) -> u32 {
+        self.block().pin_cnf[..self.pin_count()]
+            .iter()
+            .enumerate()
+            .filter(|(_, reg)| {
+                let cnf = reg.read();
+                cnf.dir().is_output() || cnf.input().is_connect() || !cnf.pull().is_disabled()
+            })
+            .fold(0, |mask, (pin, _)| mask | (1 << pin))
+    }
+
+    /// Disconnects every pin of the port that is not in the `claimed` mask.
+    ///
+    /// Together with [`Pin::into_disconnected`] for the claimed pins that are no longer needed,
+    /// this leaves the port in a known low-power state before entering System OFF. Pins owned by
+    /// a [`Pin`] or a driver must be part of `claimed`, as their configuration is overridden
+    /// otherwise. On the nRF5340, so must the pins used by the other core.
+    ///
+    /// Only the direction, input buffer and pull of the pins are changed, so that their drive,
+    /// sense and, on the nRF5340, their assignment to a core or peripheral (`MCUSEL`) are kept.
+    pub fn park_unclaimed(&self, claimed: u32) {
+        for (pin, reg) in self.block().pin_cnf[..self.pin_count()].iter().enumerate() {
+            if claimed & (1 << pin) == 0 {
+                reg.modify(|_, w| {
+                    w.dir().input();
+                    w.input().disconnect();
+                    w.pull().disabled();
+                    w
+                });
+            }
+        }
+    }
+}
+
+/// The configuration of the pins of a port, saved by [`Port::snapshot`].
+#[derive(Clone, Debug)]
+pub struct PortSnapshot {
+    port: Port,
+    out: u32,
+    pin_cnf: [u32; 32],
+}
+
+impl PortSnapshot {
+    /// Returns the port the snapshot was taken on.
+    pub fn port(&self) -> Port {
+        self.port
+    }
+
+    /// Returns the saved `PIN_CNF` register of `pin`.
+    pub fn pin_cnf(&self, pin: u8) -> u32 {
+        self.pin_cnf[usize::from(pin)]
+    }
+}
+
 impl<MODE> Pin<MODE> {
     fn new(port: Port, pin: u8) -> Self {
         let port_bits = match port {
@@ -173,15 +358,7 @@ impl<MODE> Pin<MODE> {
     }
 
     fn block(&self) -> &gpio::RegisterBlock {
-        let ptr = match self.port() {
-            Port::Port0 => P0::ptr(),
-            #[cfg(feature = "5340-app")]
-            Port::Port0Secure => P0_S::ptr(),
-            #[cfg(any(feature = "52833", feature = "52840", feature = "5340-net"))]
-            Port::Port1 => P1::ptr(),
-        };
-
-        unsafe { &*ptr }
+        self.port().block()
     }
 
     pub(crate) fn conf(&self) -> &gpio::PIN_CNF {
@@ -352,6 +529,55 @@ impl<MODE> Pin<MODE> {
             pin_port: self.pin_port,
         }
     }
+
+    /// Converts the pin into a [`Flex`] pin, keeping its current configuration.
+    pub fn into_flex(self) -> Flex {
+        Flex {
+            pin: Pin {
+                _mode: PhantomData,
+                pin_port: self.pin_port,
+            },
+        }
+    }
+}
+
+impl<MODE> Pin<Input<MODE>> {
+    /// Configures the sense mechanism of the pin.
+    ///
+    /// A pin whose sense condition is met drives the DETECT signal of its port, which triggers
+    /// the GPIOTE PORT event and wakes the chip up from System OFF.
+    pub fn set_sense(&mut self, sense: Sense) {
+        self.conf().modify(|_, w| match sense {
+            Sense::Disabled => w.sense().disabled(),
+            Sense::High => w.sense().high(),
+            Sense::Low => w.sense().low(),
+        });
+    }
+
+    /// Returns the current sense configuration of the pin.
+    pub fn sense(&self) -> Sense {
+        let sense = self.conf().read().sense();
+        if sense.is_high() {
+            Sense::High
+        } else if sense.is_low() {
+            Sense::Low
+        } else {
+            Sense::Disabled
+        }
+    }
+
+    /// Returns `true` if the sense condition of the pin has been met since the latch was last
+    /// cleared.
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    pub fn is_latched(&self) -> bool {
+        self.port().latch() & (1 << self.pin()) != 0
+    }
+
+    /// Clears the latch of the pin.
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    pub fn clear_latch(&mut self) {
+        self.port().clear_latch(1 << self.pin());
+    }
 }
 
 impl<MODE> ErrorType for Pin<MODE> {
@@ -476,6 +702,330 @@ impl<MODE> embedded_hal_02::digital::v2::StatefulOutputPin for Pin<Output<MODE>>
     }
 }
 
+/// A pin whose direction, pull and drive are configured at runtime.
+///
+/// Useful for bidirectional protocols, such as 1-Wire, that switch between driving and reading
+/// the pin. The input buffer stays connected in output mode, so the actual level of the pin can
+/// always be read.
+pub struct Flex {
+    pin: Pin<Disconnected>,
+}
+
+impl Flex {
+    /// Configures the pin as an input with the given pull resistor.
+    pub fn set_as_input(&mut self, pull: Pull) {
+        self.pin.conf().write(|w| {
+            w.dir().input();
+            w.input().connect();
+            match pull {
+                Pull::None => w.pull().disabled(),
+                Pull::Up => w.pull().pullup(),
+                Pull::Down => w.pull().pulldown(),
+            };
+            w.drive().s0s1();
+            w.sense().disabled();
+            w
+        });
+    }
+
+    /// Configures the pin as a push-pull output with the given drive.
+    ///
+    /// The pin is driven at the level last set with the [`OutputPin`] methods.
+    pub fn set_as_output(&mut self, drive: DriveConfig) {
+        self.pin.conf().write(|w| {
+            w.dir().output();
+            w.input().connect();
+            w.pull().disabled();
+            match drive {
+                DriveConfig::Standard0Standard1 => w.drive().s0s1(),
+                DriveConfig::Standard0HighDrive1 => w.drive().s0h1(),
+                DriveConfig::HighDrive0Standard1 => w.drive().h0s1(),
+                DriveConfig::HighDrive0HighDrive1 => w.drive().h0h1(),
+            };
+            w.sense().disabled();
+            w
+        });
+    }
+
+    /// Configures the pin as an open-drain output with the given pull resistor.
+    ///
+    /// The pin is driven at the level last set with the [`OutputPin`] methods.
+    pub fn set_as_open_drain(&mut self, config: OpenDrainConfig, pull: Pull) {
+        self.pin.conf().write(|w| {
+            w.dir().output();
+            w.input().connect();
+            match pull {
+                Pull::None => w.pull().disabled(),
+                Pull::Up => w.pull().pullup(),
+                Pull::Down => w.pull().pulldown(),
+            };
+            w.drive().variant(config.variant());
+            w.sense().disabled();
+            w
+        });
+    }
+
+    /// Disconnects the pin.
+    pub fn set_as_disconnected(&mut self) {
+        // Reset value is disconnected.
+        self.pin.conf().reset();
+    }
+
+    /// Returns `true` if the pin is configured as an output.
+    pub fn is_output(&self) -> bool {
+        self.pin.conf().read().dir().is_output()
+    }
+
+    /// Sets the level the pin is driven at, as an output.
+    pub fn set_level(&mut self, level: Level) {
+        match level {
+            Level::Low => self.set_low().unwrap(),
+            Level::High => self.set_high().unwrap(),
+        }
+    }
+
+    #[inline]
+    pub fn pin(&self) -> u8 {
+        self.pin.pin()
+    }
+
+    #[inline]
+    pub fn port(&self) -> Port {
+        self.pin.port()
+    }
+
+    #[inline]
+    pub fn psel_bits(&self) -> u32 {
+        self.pin.psel_bits()
+    }
+
+    /// Convert the pin to be a floating input
+    pub fn into_floating_input(self) -> Pin<Input<Floating>> {
+        self.pin.into_floating_input()
+    }
+
+    /// Convert the pin to be a pulled-up input
+    pub fn into_pullup_input(self) -> Pin<Input<PullUp>> {
+        self.pin.into_pullup_input()
+    }
+
+    /// Convert the pin to be a pulled-down input
+    pub fn into_pulldown_input(self) -> Pin<Input<PullDown>> {
+        self.pin.into_pulldown_input()
+    }
+
+    /// Convert the pin to be a push-pull output with specified drive.
+    pub fn into_push_pull_output_drive(
+        self,
+        initial_output: Level,
+        drive: DriveConfig,
+    ) -> Pin<Output<PushPull>> {
+        self.pin.into_push_pull_output_drive(initial_output, drive)
+    }
+
+    /// Convert the pin to be a push-pull output with specified initial output level.
+    pub fn into_push_pull_output(self, initial_output: Level) -> Pin<Output<PushPull>> {
+        self.pin.into_push_pull_output(initial_output)
+    }
+
+    /// Convert the pin to be an open-drain output.
+    pub fn into_open_drain_output(
+        self,
+        config: OpenDrainConfig,
+        initial_output: Level,
+    ) -> Pin<Output<OpenDrain>> {
+        self.pin.into_open_drain_output(config, initial_output)
+    }
+
+    /// Convert the pin to be an open-drain input/output.
+    pub fn into_open_drain_input_output(
+        self,
+        config: OpenDrainConfig,
+        initial_output: Level,
+    ) -> Pin<Output<OpenDrainIO>> {
+        self.pin
+            .into_open_drain_input_output(config, initial_output)
+    }
+
+    /// Disconnects the pin.
+    pub fn into_disconnected(self) -> Pin<Disconnected> {
+        self.pin.into_disconnected()
+    }
+}
+
+impl<MODE> From<Pin<MODE>> for Flex {
+    fn from(pin: Pin<MODE>) -> Self {
+        pin.into_flex()
+    }
+}
+
+impl ErrorType for Flex {
+    type Error = Infallible;
+}
+
+impl InputPin for Flex {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.is_low().map(|v| !v)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.pin.block().in_.read().bits() & (1 << self.pin.pin()) == 0)
+    }
+}
+
+impl OutputPin for Flex {
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        unsafe {
+            self.pin
+                .block()
+                .outset
+                .write(|w| w.bits(1u32 << self.pin.pin()));
+        }
+        Ok(())
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        unsafe {
+            self.pin
+                .block()
+                .outclr
+                .write(|w| w.bits(1u32 << self.pin.pin()));
+        }
+        Ok(())
+    }
+}
+
+impl StatefulOutputPin for Flex {
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        self.is_set_low().map(|v| !v)
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.pin.block().out.read().bits() & (1 << self.pin.pin()) == 0)
+    }
+}
+
+/// A group of pins of the same port, read or written with a single register access.
+///
+/// Bit `i` of the values read and written is the level of `pins[i]`. When the pins are
+/// consecutive, in increasing order, values are shifted into place instead of being scattered bit
+/// by bit.
+pub struct PinBus<MODE, const N: usize> {
+    pins: [Pin<MODE>; N],
+    mask: u32,
+    /// Position of the first pin, if the pins are consecutive.
+    shift: Option<u8>,
+}
+
+impl<MODE, const N: usize> PinBus<MODE, N> {
+    /// Groups `pins` into a bus.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pins` is empty, if the pins are not all on the same port, or if a pin is given
+    /// twice.
+    pub fn new(pins: [Pin<MODE>; N]) -> Self {
+        assert!(N > 0, "a PinBus needs at least one pin");
+        let port = pins[0].port();
+        let mut mask = 0;
+        for pin in &pins {
+            assert!(
+                pin.port() == port,
+                "the pins of a PinBus must be on the same port"
+            );
+            assert!(mask & (1 << pin.pin()) == 0, "a pin was given twice");
+            mask |= 1 << pin.pin();
+        }
+
+        let first = pins[0].pin();
+        let consecutive = pins
+            .iter()
+            .enumerate()
+            .all(|(i, pin)| usize::from(pin.pin()) == usize::from(first) + i);
+
+        Self {
+            pins,
+            mask,
+            shift: if consecutive { Some(first) } else { None },
+        }
+    }
+
+    /// Returns the port of the pins.
+    pub fn port(&self) -> Port {
+        self.pins[0].port()
+    }
+
+    /// Returns the mask of the pins in the registers of the port.
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+
+    /// Releases the pins.
+    pub fn free(self) -> [Pin<MODE>; N] {
+        self.pins
+    }
+
+    fn block(&self) -> &gpio::RegisterBlock {
+        self.pins[0].block()
+    }
+
+    /// Converts the bits of a port register to a bus value.
+    fn gather(&self, bits: u32) -> u32 {
+        match self.shift {
+            Some(shift) => (bits & self.mask) >> shift,
+            None => self
+                .pins
+                .iter()
+                .enumerate()
+                .fold(0, |value, (i, pin)| value | ((bits >> pin.pin()) & 1) << i),
+        }
+    }
+
+    /// Converts a bus value to the bits of a port register.
+    fn scatter(&self, value: u32) -> u32 {
+        match self.shift {
+            Some(shift) => (value << shift) & self.mask,
+            None => self
+                .pins
+                .iter()
+                .enumerate()
+                .fold(0, |bits, (i, pin)| bits | ((value >> i) & 1) << pin.pin()),
+        }
+    }
+}
+
+impl<MODE, const N: usize> PinBus<Input<MODE>, N> {
+    /// Reads the levels of the pins.
+    pub fn read(&self) -> u32 {
+        self.gather(self.block().in_.read().bits())
+    }
+}
+
+impl<const N: usize> PinBus<Output<OpenDrainIO>, N> {
+    /// Reads the levels of the pins.
+    pub fn read(&self) -> u32 {
+        self.gather(self.block().in_.read().bits())
+    }
+}
+
+impl<MODE, const N: usize> PinBus<Output<MODE>, N> {
+    /// Drives the pins to the levels of `value`, with one `OUTSET` and one `OUTCLR` write.
+    pub fn write(&mut self, value: u32) {
+        let set = self.scatter(value);
+        let block = self.block();
+        // NOTE(unsafe) atomic writes to stateless registers, limited to the pins of the bus
+        unsafe {
+            block.outset.write(|w| w.bits(set));
+            block.outclr.write(|w| w.bits(!set & self.mask));
+        }
+    }
+
+    /// Returns the levels the pins are driven to.
+    pub fn read_output(&self) -> u32 {
+        self.gather(self.block().out.read().bits())
+    }
+}
+
 /// Pin configuration for open-drain mode.
 pub enum OpenDrainConfig {
     Disconnect0Standard1,
@@ -519,10 +1069,40 @@ impl OpenDrainConfig {
     }
 }
 
+/// An NFC antenna pin: P0.09 or P0.10 on the nRF52832, nRF52833 and nRF52840, P0.02 or P0.03 on
+/// the nRF5340.
+///
+/// After an erase of the UICR, the NFC pins are protected against the voltages induced by an NFC
+/// field, and configuring them as GPIO is silently ineffective. `UICR.NFCPINS` must configure them
+/// as GPIO first, with `Uicr::set_nfc_pins_as_gpio`, and the pins are only turned into GPIO pins
+/// with the proof that it does.
+#[cfg(any(
+    feature = "52832",
+    feature = "52833",
+    feature = "52840",
+    feature = "5340-app"
+))]
+pub struct NfcPin<PIN>(PIN);
+
+#[cfg(any(
+    feature = "52832",
+    feature = "52833",
+    feature = "52840",
+    feature = "5340-app"
+))]
+impl<PIN> NfcPin<PIN> {
+    /// Returns the pin, to be used as GPIO.
+    ///
+    /// The proof is obtained with [`Uicr::nfc_pins_gpio`](crate::uicr::Uicr::nfc_pins_gpio).
+    pub fn into_gpio(self, _proof: crate::uicr::NfcPinsGpio) -> PIN {
+        self.0
+    }
+}
+
 macro_rules! gpio {
     (
         $PX:ident, $pxsvd:ident, $px:ident, $port_value:expr, [
-            $($PXi:ident: ($pxi:ident, $i:expr, $MODE:ty),)+
+            $($PXi:ident: ($pxi:ident, $i:expr, $MODE:ty) $(nfc($nfc:meta))?,)+
         ]
     ) => {
         /// GPIO
@@ -543,6 +1123,7 @@ macro_rules! gpio {
                 PullUp,
                 PushPull,
                 OpenDrainIO,
+                Sense,
 
                 PhantomData,
                 $PX
@@ -559,7 +1140,13 @@ macro_rules! gpio {
             pub struct Parts {
                 $(
                     /// Pin
+                    $(#[cfg(not($nfc))])?
                     pub $pxi: $PXi<$MODE>,
+                    $(
+                        /// NFC antenna pin
+                        #[cfg($nfc)]
+                        pub $pxi: super::NfcPin<$PXi<$MODE>>,
+                    )?
                 )+
             }
 
@@ -567,9 +1154,16 @@ macro_rules! gpio {
                 pub fn new(_gpio: $PX) -> Self {
                     Self {
                         $(
+                            $(#[cfg(not($nfc))])?
                             $pxi: $PXi {
                                 _mode: PhantomData,
                             },
+                            $(
+                                #[cfg($nfc)]
+                                $pxi: super::NfcPin($PXi {
+                                    _mode: PhantomData,
+                                }),
+                            )?
                         )+
                     }
                 }
@@ -760,6 +1354,17 @@ macro_rules! gpio {
                     }
                 }
 
+                impl<MODE> $PXi<Input<MODE>> {
+                    /// Configures the sense mechanism of the pin.
+                    pub fn set_sense(&mut self, sense: Sense) {
+                        unsafe { &(*$PX::ptr()).pin_cnf[$i] }.modify(|_, w| match sense {
+                            Sense::Disabled => w.sense().disabled(),
+                            Sense::High => w.sense().high(),
+                            Sense::Low => w.sense().low(),
+                        });
+                    }
+                }
+
                 impl<MODE> ErrorType for $PXi<MODE> {
                     type Error = Infallible;
                 }
@@ -892,15 +1497,15 @@ macro_rules! gpio {
 gpio!(P0, p0, p0, Port::Port0, [
     P0_00: (p0_00,  0, Disconnected),
     P0_01: (p0_01,  1, Disconnected),
-    P0_02: (p0_02,  2, Disconnected),
-    P0_03: (p0_03,  3, Disconnected),
+    P0_02: (p0_02,  2, Disconnected) nfc(feature = "5340-app"),
+    P0_03: (p0_03,  3, Disconnected) nfc(feature = "5340-app"),
     P0_04: (p0_04,  4, Disconnected),
     P0_05: (p0_05,  5, Disconnected),
     P0_06: (p0_06,  6, Disconnected),
     P0_07: (p0_07,  7, Disconnected),
     P0_08: (p0_08,  8, Disconnected),
-    P0_09: (p0_09,  9, Disconnected),
-    P0_10: (p0_10, 10, Disconnected),
+    P0_09: (p0_09,  9, Disconnected) nfc(any(feature = "52832", feature = "52833", feature = "52840")),
+    P0_10: (p0_10, 10, Disconnected) nfc(any(feature = "52832", feature = "52833", feature = "52840")),
     P0_11: (p0_11, 11, Disconnected),
     P0_12: (p0_12, 12, Disconnected),
     P0_13: (p0_13, 13, Disconnected),
@@ -950,8 +1555,8 @@ gpio!(P1, p0, p1, Port::Port1, [
 gpio!(P0_S, p0, p0_s, Port::Port0Secure, [
     P0_00: (p0_00,  0, Disconnected),
     P0_01: (p0_01,  1, Disconnected),
-    P0_02: (p0_02,  2, Disconnected),
-    P0_03: (p0_03,  3, Disconnected),
+    P0_02: (p0_02,  2, Disconnected) nfc(feature = "5340-app"),
+    P0_03: (p0_03,  3, Disconnected) nfc(feature = "5340-app"),
     P0_04: (p0_04,  4, Disconnected),
     P0_05: (p0_05,  5, Disconnected),
     P0_06: (p0_06,  6, Disconnected),
@@ -2,6 +2,14 @@
 //!
 //! The GPIO tasks and events (GPIOTE) module provides functionality for accessing GPIO pins using
 //! tasks and events.
+//!
+//! Channels can be borrowed by number, with [`Gpiote::channel0`] and its siblings, or allocated
+//! with [`Gpiote::alloc_channel`]. An allocated [`Channel`] is owned, and turned into an
+//! [`InputChannel`] or an [`OutputChannel`] that keeps its pin, releasing both on drop.
+//!
+//! With the `embedded-hal-async` feature, input pins implement the `embedded-hal-async` `Wait`
+//! trait, using GPIOTE channels allocated for the duration of each wait, or the PORT event when no
+//! channel is free. [`on_interrupt`] must then be called from the `GPIOTE` interrupt handler.
 
 #[cfg(feature = "51")]
 use crate::pac::GPIO as P0;
@@ -18,31 +26,45 @@ use crate::pac::P1;
 #[cfg(feature = "5340-net")]
 use crate::pac::P1_NS as P1;
 
-use crate::gpio::{
-    Floating, Input, Level, OpenDrain, Output, Pin, Port, PullDown, PullUp, PushPull,
-};
+use crate::gpio::{Input, Level, OpenDrain, Output, Pin, Port, PushPull};
 
 #[cfg(not(any(feature = "9160", feature = "5340-net")))]
 use {
-    crate::pac::gpiote::{EVENTS_IN, EVENTS_PORT, TASKS_OUT},
+    crate::pac::gpiote::{RegisterBlock, EVENTS_IN, EVENTS_PORT, TASKS_OUT},
     crate::pac::GPIOTE,
 };
 
 #[cfg(feature = "9160")]
 use {
-    crate::pac::gpiote0_s::{EVENTS_IN, EVENTS_PORT, TASKS_CLR, TASKS_OUT, TASKS_SET},
+    crate::pac::gpiote0_s::{
+        RegisterBlock, EVENTS_IN, EVENTS_PORT, TASKS_CLR, TASKS_OUT, TASKS_SET,
+    },
     crate::pac::GPIOTE1_NS as GPIOTE,
 };
 
 #[cfg(feature = "5340-net")]
 use {
-    crate::pac::gpiote_ns::{EVENTS_IN, EVENTS_PORT, TASKS_CLR, TASKS_OUT, TASKS_SET},
+    crate::pac::gpiote_ns::{
+        RegisterBlock, EVENTS_IN, EVENTS_PORT, TASKS_CLR, TASKS_OUT, TASKS_SET,
+    },
     crate::pac::GPIOTE_NS as GPIOTE,
 };
 
 #[cfg(not(any(feature = "51", feature = "9160", feature = "5340-net")))]
 use crate::pac::gpiote::{TASKS_CLR, TASKS_SET};
 
+use core::cell::Cell;
+use cortex_m::interrupt::{self, Mutex};
+
+#[cfg(feature = "embedded-hal-async")]
+use {
+    crate::asynch::{AtomicWaker, OnDrop},
+    crate::gpio::{Floating, Sense},
+    core::{future::poll_fn, task::Poll},
+    cortex_m::interrupt::CriticalSection,
+    embedded_hal::digital::InputPin,
+};
+
 #[cfg(not(feature = "51"))]
 const NUM_CHANNELS: usize = 8;
 #[cfg(feature = "51")]
@@ -59,12 +81,25 @@ impl Gpiote {
         Self { gpiote }
     }
 
+    /// Allocates a channel that is neither allocated nor configured through the borrowed channels
+    /// of [`Gpiote::channel0`] and its siblings.
+    ///
+    /// Returns `None` if all channels are in use. The channel is freed when dropped.
+    pub fn alloc_channel(&self) -> Option<Channel> {
+        alloc_channel().map(|channel| Channel { channel })
+    }
+
     fn channel(&self, channel: usize) -> GpioteChannel<'_> {
         GpioteChannel {
             gpiote: &self.gpiote,
             channel,
         }
     }
+
+    /// Borrows channel 0.
+    ///
+    /// Borrowed channels are not tracked: configuring a channel that has been allocated with
+    /// [`Gpiote::alloc_channel`], or by an async wait, clobbers its configuration.
     pub fn channel0(&self) -> GpioteChannel<'_> {
         self.channel(0)
     }
@@ -254,7 +289,7 @@ impl<P: GpioteInputPin> GpioteChannelEvent<'_, P> {
 }
 
 fn config_channel_event_pin<P: GpioteInputPin>(
-    gpiote: &GPIOTE,
+    gpiote: &RegisterBlock,
     channel: usize,
     pin: &P,
     trigger_mode: EventPolarity,
@@ -354,7 +389,7 @@ impl<P: GpioteOutputPin> GpioteTask<'_, P> {
 }
 
 fn config_channel_task_pin<P: GpioteOutputPin>(
-    gpiote: &GPIOTE,
+    gpiote: &RegisterBlock,
     channel: usize,
     pin: &P,
     task_out_polarity: &TaskOutPolarity,
@@ -410,7 +445,7 @@ pub trait GpioteInputPin {
     fn port(&self) -> Port;
 }
 
-impl GpioteInputPin for Pin<Input<PullUp>> {
+impl<MODE> GpioteInputPin for Pin<Input<MODE>> {
     fn pin(&self) -> u8 {
         self.pin()
     }
@@ -419,7 +454,13 @@ impl GpioteInputPin for Pin<Input<PullUp>> {
     }
 }
 
-impl GpioteInputPin for Pin<Input<PullDown>> {
+/// Trait to represent task output pin.
+pub trait GpioteOutputPin {
+    fn pin(&self) -> u8;
+    fn port(&self) -> Port;
+}
+
+impl GpioteOutputPin for Pin<Output<OpenDrain>> {
     fn pin(&self) -> u8 {
         self.pin()
     }
@@ -428,7 +469,7 @@ impl GpioteInputPin for Pin<Input<PullDown>> {
     }
 }
 
-impl GpioteInputPin for Pin<Input<Floating>> {
+impl GpioteOutputPin for Pin<Output<PushPull>> {
     fn pin(&self) -> u8 {
         self.pin()
     }
@@ -437,26 +478,437 @@ impl GpioteInputPin for Pin<Input<Floating>> {
     }
 }
 
-/// Trait to represent task output pin.
-pub trait GpioteOutputPin {
-    fn pin(&self) -> u8;
-    fn port(&self) -> Port;
+/// Allocated channels, owned by a [`Channel`].
+static ALLOCATED: Mutex<Cell<u8>> = Mutex::new(Cell::new(0));
+
+fn regs() -> &'static RegisterBlock {
+    unsafe { &*GPIOTE::ptr() }
 }
 
-impl GpioteOutputPin for Pin<Output<OpenDrain>> {
-    fn pin(&self) -> u8 {
-        self.pin()
+fn alloc_channel() -> Option<usize> {
+    let gpiote = regs();
+    interrupt::free(|cs| {
+        let allocated = ALLOCATED.borrow(cs);
+        let channel = (0..NUM_CHANNELS).find(|&ch| {
+            allocated.get() & (1 << ch) == 0 && gpiote.config[ch].read().mode().is_disabled()
+        })?;
+        allocated.set(allocated.get() | (1 << channel));
+        Some(channel)
+    })
+}
+
+/// An owned GPIOTE channel, allocated with [`Gpiote::alloc_channel`].
+///
+/// Dropping the channel disables it and returns it to the pool of free channels.
+pub struct Channel {
+    channel: usize,
+}
+
+impl Channel {
+    /// Returns the number of the channel.
+    pub fn number(&self) -> usize {
+        self.channel
+    }
+
+    /// Configures the channel to generate events on the `polarity` transitions of `pin`.
+    pub fn into_input<MODE>(
+        self,
+        pin: Pin<Input<MODE>>,
+        polarity: EventPolarity,
+    ) -> InputChannel<MODE> {
+        config_channel_event_pin(regs(), self.channel, &pin, polarity);
+        InputChannel { channel: self, pin }
+    }
+
+    /// Configures the channel to drive `pin` through its tasks, starting at `initial_output`.
+    pub fn into_output<MODE>(
+        self,
+        pin: Pin<Output<MODE>>,
+        polarity: TaskOutPolarity,
+        initial_output: Level,
+    ) -> OutputChannel<MODE>
+    where
+        Pin<Output<MODE>>: GpioteOutputPin,
+    {
+        config_channel_task_pin(regs(), self.channel, &pin, &polarity, initial_output);
+        OutputChannel { channel: self, pin }
+    }
+
+    /// Disables the channel, releasing its pin.
+    fn disable(&self) {
+        let gpiote = regs();
+        gpiote
+            .intenclr
+            .write(|w| unsafe { w.bits(1 << self.channel) });
+        gpiote.config[self.channel].reset();
+        gpiote.events_in[self.channel].write(|w| w);
     }
-    fn port(&self) -> Port {
-        self.port()
+}
+
+impl Drop for Channel {
+    fn drop(&mut self) {
+        self.disable();
+        #[cfg(feature = "embedded-hal-async")]
+        set_waiting(self.channel, false);
+        interrupt::free(|cs| {
+            let allocated = ALLOCATED.borrow(cs);
+            allocated.set(allocated.get() & !(1 << self.channel));
+        });
     }
 }
 
-impl GpioteOutputPin for Pin<Output<PushPull>> {
-    fn pin(&self) -> u8 {
-        self.pin()
+/// An owned channel generating events from the transitions of its pin.
+pub struct InputChannel<MODE> {
+    channel: Channel,
+    pin: Pin<Input<MODE>>,
+}
+
+impl<MODE> InputChannel<MODE> {
+    /// Returns the pin of the channel.
+    pub fn pin(&self) -> &Pin<Input<MODE>> {
+        &self.pin
     }
-    fn port(&self) -> Port {
-        self.port()
+
+    /// Checks if the channel event has been triggered.
+    pub fn is_event_triggered(&self) -> bool {
+        regs().events_in[self.channel.channel].read().bits() != 0
+    }
+
+    /// Resets the channel event.
+    pub fn reset_events(&self) {
+        regs().events_in[self.channel.channel].write(|w| w);
+    }
+
+    /// Enables the GPIOTE interrupt for the channel.
+    pub fn enable_interrupt(&self) {
+        regs()
+            .intenset
+            .write(|w| unsafe { w.bits(1 << self.channel.channel) });
+    }
+
+    /// Disables the GPIOTE interrupt for the channel.
+    pub fn disable_interrupt(&self) {
+        regs()
+            .intenclr
+            .write(|w| unsafe { w.bits(1 << self.channel.channel) });
+    }
+
+    /// Returns reference to the channel event endpoint for PPI.
+    pub fn event(&self) -> &EVENTS_IN {
+        &regs().events_in[self.channel.channel]
+    }
+
+    /// Disables the channel, returning it and the pin.
+    pub fn free(self) -> (Channel, Pin<Input<MODE>>) {
+        self.channel.disable();
+        (self.channel, self.pin)
+    }
+}
+
+/// An owned channel driving its pin through the channel tasks.
+pub struct OutputChannel<MODE> {
+    channel: Channel,
+    pin: Pin<Output<MODE>>,
+}
+
+impl<MODE> OutputChannel<MODE> {
+    /// Returns the pin of the channel.
+    pub fn pin(&self) -> &Pin<Output<MODE>> {
+        &self.pin
+    }
+
+    /// Triggers `task out`, as configured by the polarity of the channel.
+    pub fn out(&self) {
+        regs().tasks_out[self.channel.channel].write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Triggers `task set` (sets the pin high).
+    #[cfg(not(feature = "51"))]
+    pub fn set(&self) {
+        regs().tasks_set[self.channel.channel].write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Triggers `task clear` (sets the pin low).
+    #[cfg(not(feature = "51"))]
+    pub fn clear(&self) {
+        regs().tasks_clr[self.channel.channel].write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Returns reference to the task_out endpoint for PPI.
+    pub fn task_out(&self) -> &TASKS_OUT {
+        &regs().tasks_out[self.channel.channel]
+    }
+
+    /// Returns reference to the task_clr endpoint for PPI.
+    #[cfg(not(feature = "51"))]
+    pub fn task_clr(&self) -> &TASKS_CLR {
+        &regs().tasks_clr[self.channel.channel]
+    }
+
+    /// Returns reference to the task_set endpoint for PPI.
+    #[cfg(not(feature = "51"))]
+    pub fn task_set(&self) -> &TASKS_SET {
+        &regs().tasks_set[self.channel.channel]
+    }
+
+    /// Disables the channel, returning it and the pin.
+    ///
+    /// The pin is driven by its `OUT` register again.
+    pub fn free(self) -> (Channel, Pin<Output<MODE>>) {
+        self.channel.disable();
+        (self.channel, self.pin)
+    }
+}
+
+/// Channels used by pending async waits.
+#[cfg(feature = "embedded-hal-async")]
+static WAITING: Mutex<Cell<u8>> = Mutex::new(Cell::new(0));
+
+#[cfg(feature = "embedded-hal-async")]
+static WAKERS: [AtomicWaker; NUM_CHANNELS] = [const { AtomicWaker::new() }; NUM_CHANNELS];
+
+/// Number of pins, indexed by their `PSEL` bits.
+#[cfg(all(
+    feature = "embedded-hal-async",
+    any(feature = "52833", feature = "52840", feature = "5340-net")
+))]
+const NUM_PINS: usize = 64;
+#[cfg(all(
+    feature = "embedded-hal-async",
+    not(any(feature = "52833", feature = "52840", feature = "5340-net"))
+))]
+const NUM_PINS: usize = 32;
+
+/// Pins, by `PSEL` bits, sensed by pending async waits that use the PORT event.
+#[cfg(feature = "embedded-hal-async")]
+static PORT_WAITING: Mutex<Cell<u64>> = Mutex::new(Cell::new(0));
+
+#[cfg(feature = "embedded-hal-async")]
+static PORT_WAKERS: [AtomicWaker; NUM_PINS] = [const { AtomicWaker::new() }; NUM_PINS];
+
+/// Whether the PORT interrupt was already enabled when async waits started using it.
+#[cfg(feature = "embedded-hal-async")]
+static PORT_INTEN_KEPT: Mutex<Cell<bool>> = Mutex::new(Cell::new(false));
+
+/// Handles the GPIOTE interrupt for the async `Wait` implementation of input pins.
+///
+/// This must be called from the `GPIOTE` interrupt handler when using the `embedded-hal-async`
+/// implementation, and the interrupt must be unmasked in the NVIC. Other channels are left
+/// untouched, so their events can be handled in the same interrupt handler. The PORT event is
+/// reset while async waits use it.
+#[cfg(feature = "embedded-hal-async")]
+pub fn on_interrupt() {
+    let gpiote = regs();
+    let waiting = interrupt::free(|cs| WAITING.borrow(cs).get());
+    for (channel, waker) in WAKERS.iter().enumerate() {
+        if waiting & (1 << channel) != 0 && gpiote.events_in[channel].read().bits() != 0 {
+            gpiote.intenclr.write(|w| unsafe { w.bits(1 << channel) });
+            waker.wake();
+        }
+    }
+
+    let port_waiting = interrupt::free(|cs| PORT_WAITING.borrow(cs).get());
+    if port_waiting != 0 && gpiote.events_port.read().bits() != 0 {
+        gpiote.events_port.write(|w| w);
+        // Disabling the sense of the pins whose condition is met lowers DETECT, so that the next
+        // pin to meet its condition triggers a new PORT event. A pin can meet its condition while
+        // the others are scanned, so the pins are scanned until none does.
+        loop {
+            let mut done = true;
+            for (psel, waker) in PORT_WAKERS.iter().enumerate() {
+                interrupt::free(|cs| {
+                    if PORT_WAITING.borrow(cs).get() & (1 << psel) == 0 {
+                        return;
+                    }
+                    // NOTE(unsafe) only the sense of a pin waited on is changed
+                    let mut pin = unsafe { Pin::<Input<Floating>>::from_psel_bits(psel as u32) };
+                    let met = match pin.sense() {
+                        Sense::High => pin.is_high().unwrap(),
+                        Sense::Low => pin.is_low().unwrap(),
+                        Sense::Disabled => false,
+                    };
+                    if met {
+                        // The wait restores the sense of the pin when it completes.
+                        pin.set_sense(Sense::Disabled);
+                        set_port_waiting(cs, psel, false);
+                        waker.wake();
+                        done = false;
+                    }
+                });
+            }
+            if done {
+                break;
+            }
+        }
+    }
+}
+
+/// Marks `psel` as waited on through the PORT event, or not.
+///
+/// The PORT interrupt is enabled while any pin is waited on, and disabled again afterwards unless
+/// it was already enabled before.
+#[cfg(feature = "embedded-hal-async")]
+fn set_port_waiting(cs: &CriticalSection, psel: usize, waiting: bool) {
+    let gpiote = regs();
+    let mask = PORT_WAITING.borrow(cs);
+    let old = mask.get();
+    let new = if waiting {
+        old | (1 << psel)
+    } else {
+        old & !(1 << psel)
+    };
+    mask.set(new);
+
+    if old == 0 && new != 0 {
+        PORT_INTEN_KEPT
+            .borrow(cs)
+            .set(gpiote.intenset.read().port().bit_is_set());
+        gpiote.intenset.write(|w| w.port().set());
+    } else if old != 0 && new == 0 && !PORT_INTEN_KEPT.borrow(cs).get() {
+        gpiote.intenclr.write(|w| w.port().set_bit());
+    }
+}
+
+/// Waits for `pin` to be `high`, through its sense mechanism and the PORT event.
+#[cfg(feature = "embedded-hal-async")]
+async fn wait_for_level<MODE>(pin: &mut Pin<Input<MODE>>, high: bool) {
+    if pin.is_high().unwrap() == high {
+        return;
+    }
+
+    let psel = pin.psel_bits() as usize;
+    let sense = pin.sense();
+    let _guard = OnDrop::new(move || {
+        interrupt::free(|cs| {
+            set_port_waiting(cs, psel, false);
+            // NOTE(unsafe) the pin is borrowed by the wait
+            unsafe { Pin::<Input<Floating>>::from_psel_bits(psel as u32) }.set_sense(sense);
+        });
+    });
+
+    interrupt::free(|cs| set_port_waiting(cs, psel, true));
+    // The PORT event is triggered right away if the pin reached the level since it was read.
+    pin.set_sense(if high { Sense::High } else { Sense::Low });
+
+    poll_fn(|cx| {
+        PORT_WAKERS[psel].register(cx.waker());
+        // The interrupt handler stops waiting on the pin once its level is reached.
+        if interrupt::free(|cs| PORT_WAITING.borrow(cs).get()) & (1 << psel) == 0 {
+            return Poll::Ready(());
+        }
+        Poll::Pending
+    })
+    .await;
+}
+
+/// Waits on the PORT event, when no channel is free.
+///
+/// The sense mechanism only detects levels, so an edge is detected as a level followed by the
+/// opposite one.
+#[cfg(feature = "embedded-hal-async")]
+async fn wait_for_port_event<MODE>(
+    pin: &mut Pin<Input<MODE>>,
+    polarity: EventPolarity,
+    level: Option<bool>,
+) {
+    match (polarity, level) {
+        (_, Some(high)) => wait_for_level(pin, high).await,
+        (EventPolarity::LoToHi, None) => {
+            wait_for_level(pin, false).await;
+            wait_for_level(pin, true).await;
+        }
+        (EventPolarity::HiToLo, None) => {
+            wait_for_level(pin, true).await;
+            wait_for_level(pin, false).await;
+        }
+        (_, None) => {
+            let high = pin.is_high().unwrap();
+            wait_for_level(pin, !high).await;
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal-async")]
+fn set_waiting(channel: usize, waiting: bool) {
+    interrupt::free(|cs| {
+        let mask = WAITING.borrow(cs);
+        if waiting {
+            mask.set(mask.get() | (1 << channel));
+        } else {
+            mask.set(mask.get() & !(1 << channel));
+        }
+    });
+}
+
+/// Waits for the `polarity` edge on `pin`, or for `pin` to be at the `level` it leads to.
+#[cfg(feature = "embedded-hal-async")]
+async fn wait_for_event<MODE>(
+    pin: &mut Pin<Input<MODE>>,
+    polarity: EventPolarity,
+    level: Option<bool>,
+) {
+    let gpiote = regs();
+    // Dropping the channel, at the end of the wait or when the future is dropped, frees it.
+    let channel = match alloc_channel() {
+        Some(channel) => Channel { channel },
+        None => return wait_for_port_event(pin, polarity, level).await,
+    };
+
+    config_channel_event_pin(gpiote, channel.channel, &*pin, polarity);
+    gpiote.events_in[channel.channel].write(|w| w);
+
+    // The level is checked after the channel is configured, so an edge cannot be missed.
+    if let Some(high) = level {
+        if pin.is_high().unwrap() == high {
+            return;
+        }
+    }
+
+    set_waiting(channel.channel, true);
+    poll_fn(|cx| {
+        WAKERS[channel.channel].register(cx.waker());
+        if gpiote.events_in[channel.channel].read().bits() != 0 {
+            return Poll::Ready(());
+        }
+        gpiote
+            .intenset
+            .write(|w| unsafe { w.bits(1 << channel.channel) });
+        Poll::Pending
+    })
+    .await;
+}
+
+/// Async implementation, driven by the GPIOTE interrupt.
+///
+/// See [`on_interrupt`] for the required interrupt handler. Each wait allocates a channel, as
+/// [`Gpiote::alloc_channel`] does, until it completes or is dropped. If no channel is free, the
+/// wait uses the sense mechanism of the pin and the PORT event instead, restoring the sense
+/// configuration of the pin once done. This uses less power, but can miss pulses shorter than the interrupt
+/// latency when waiting for an edge.
+#[cfg(feature = "embedded-hal-async")]
+impl<MODE> embedded_hal_async::digital::Wait for Pin<Input<MODE>> {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        wait_for_event(self, EventPolarity::LoToHi, Some(true)).await;
+        Ok(())
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        wait_for_event(self, EventPolarity::HiToLo, Some(false)).await;
+        Ok(())
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        wait_for_event(self, EventPolarity::LoToHi, None).await;
+        Ok(())
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        wait_for_event(self, EventPolarity::HiToLo, None).await;
+        Ok(())
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        wait_for_event(self, EventPolarity::Toggle, None).await;
+        Ok(())
     }
 }
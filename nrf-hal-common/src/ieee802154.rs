@@ -1,4 +1,10 @@
 //! IEEE 802.15.4 radio
+//!
+//! Available on the nRF52811, nRF52833, nRF52840 and the network core of the nRF5340. The set of
+//! [`TxPower`] values depends on the chip.
+//!
+//! Frames can be acknowledged in hardware, and timestamped, with [`Radio::recv_with_ack`] and an
+//! [`AutoAck`], which uses a TIMER and three (D)PPI channels.
 
 use core::{
     marker::PhantomData,
@@ -8,12 +14,23 @@ use core::{
 
 use nb::block;
 
+#[cfg(feature = "5340-net")]
+use crate::dppi::{Dppi as PpiChannel, Publish, Subscribe};
+#[cfg(not(feature = "5340-net"))]
+use crate::pac::{
+    radio::{state::STATE_A, txpower::TXPOWER_A},
+    RADIO,
+};
+#[cfg(feature = "5340-net")]
+use crate::pac::{
+    radio_ns::{state::STATE_A, txpower::TXPOWER_A},
+    RADIO_NS as RADIO,
+};
+#[cfg(not(feature = "5340-net"))]
+use crate::ppi::{ConfigurablePpi as PpiChannel, Ppi};
 use crate::{
     clocks::{Clocks, ExternalOscillator},
-    pac::{
-        radio::{state::STATE_A, txpower::TXPOWER_A},
-        RADIO,
-    },
+    rng::Rng,
     timer::{self, Timer},
 };
 
@@ -138,28 +155,58 @@ pub enum Channel {
 }
 
 /// Transmission power in dBm (decibel milliwatt)
+///
+/// The available values depend on the chip. On the nRF5340, +1 dBm to +3 dBm require the high
+/// voltage mode of the radio, enabled through `VREQCTRL`.
 // TXPOWERA enum minus the deprecated Neg30dBm variant and with better docs
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum TxPower {
     /// +8 dBm
+    #[cfg(any(feature = "52833", feature = "52840"))]
     Pos8dBm,
     /// +7 dBm
+    #[cfg(any(feature = "52833", feature = "52840"))]
     Pos7dBm,
     /// +6 dBm (~4 mW)
+    #[cfg(any(feature = "52833", feature = "52840"))]
     Pos6dBm,
     /// +5 dBm
+    #[cfg(any(feature = "52833", feature = "52840"))]
     Pos5dBm,
     /// +4 dBm
+    #[cfg(any(feature = "52811", feature = "52833", feature = "52840"))]
     Pos4dBm,
     /// +3 dBm (~2 mW)
     Pos3dBm,
     /// +2 dBm
+    #[cfg(any(feature = "52833", feature = "52840", feature = "5340-net"))]
     Pos2dBm,
+    /// +1 dBm
+    #[cfg(feature = "5340-net")]
+    Pos1dBm,
     /// 0 dBm (1 mW)
     _0dBm,
+    /// -1 dBm
+    #[cfg(feature = "5340-net")]
+    Neg1dBm,
+    /// -2 dBm
+    #[cfg(feature = "5340-net")]
+    Neg2dBm,
+    /// -3 dBm
+    #[cfg(feature = "5340-net")]
+    Neg3dBm,
     /// -4 dBm
     Neg4dBm,
+    /// -5 dBm
+    #[cfg(feature = "5340-net")]
+    Neg5dBm,
+    /// -6 dBm
+    #[cfg(feature = "5340-net")]
+    Neg6dBm,
+    /// -7 dBm
+    #[cfg(feature = "5340-net")]
+    Neg7dBm,
     /// -8 dBm
     Neg8dBm,
     /// -12 dBm
@@ -180,14 +227,34 @@ impl TxPower {
             TxPower::Neg16dBm => TXPOWER_A::NEG16D_BM,
             TxPower::Neg12dBm => TXPOWER_A::NEG12D_BM,
             TxPower::Neg8dBm => TXPOWER_A::NEG8D_BM,
+            #[cfg(feature = "5340-net")]
+            TxPower::Neg7dBm => TXPOWER_A::NEG7D_BM,
+            #[cfg(feature = "5340-net")]
+            TxPower::Neg6dBm => TXPOWER_A::NEG6D_BM,
+            #[cfg(feature = "5340-net")]
+            TxPower::Neg5dBm => TXPOWER_A::NEG5D_BM,
             TxPower::Neg4dBm => TXPOWER_A::NEG4D_BM,
+            #[cfg(feature = "5340-net")]
+            TxPower::Neg3dBm => TXPOWER_A::NEG3D_BM,
+            #[cfg(feature = "5340-net")]
+            TxPower::Neg2dBm => TXPOWER_A::NEG2D_BM,
+            #[cfg(feature = "5340-net")]
+            TxPower::Neg1dBm => TXPOWER_A::NEG1D_BM,
             TxPower::_0dBm => TXPOWER_A::_0D_BM,
+            #[cfg(feature = "5340-net")]
+            TxPower::Pos1dBm => TXPOWER_A::POS1D_BM,
+            #[cfg(any(feature = "52833", feature = "52840", feature = "5340-net"))]
             TxPower::Pos2dBm => TXPOWER_A::POS2D_BM,
             TxPower::Pos3dBm => TXPOWER_A::POS3D_BM,
+            #[cfg(any(feature = "52811", feature = "52833", feature = "52840"))]
             TxPower::Pos4dBm => TXPOWER_A::POS4D_BM,
+            #[cfg(any(feature = "52833", feature = "52840"))]
             TxPower::Pos5dBm => TXPOWER_A::POS5D_BM,
+            #[cfg(any(feature = "52833", feature = "52840"))]
             TxPower::Pos6dBm => TXPOWER_A::POS6D_BM,
+            #[cfg(any(feature = "52833", feature = "52840"))]
             TxPower::Pos7dBm => TXPOWER_A::POS7D_BM,
+            #[cfg(any(feature = "52833", feature = "52840"))]
             TxPower::Pos8dBm => TXPOWER_A::POS8D_BM,
         }
     }
@@ -425,10 +492,144 @@ impl<'c> Radio<'c> {
         })
     }
 
+    /// Receives one radio packet into the given `packet` buffer, and acknowledges it in hardware
+    ///
+    /// The packet is acknowledged if its CRC is valid, it requests an acknowledgment and it is
+    /// addressed to the device configured in `ack`. The acknowledgment is started by the TIMER of
+    /// `ack` exactly aTurnaroundTime (192 μs) after the end of the packet, the transmitter being
+    /// ramped up in the meantime. Only frames of the 2003 and 2006 versions of the standard are
+    /// acknowledged, as the 2015 version requires enhanced acknowledgments. If this method is
+    /// delayed past the turnaround time, for instance by an interrupt, no acknowledgment is sent.
+    ///
+    /// The SFD timestamp of the packet is recorded in `packet`, see [`Packet::timestamp`].
+    ///
+    /// If `timeout` is `Some`, no more than this amount of microseconds is waited for the start of
+    /// a packet, after which the `Timeout` error is returned. A packet whose reception started
+    /// before the timeout is received entirely.
+    pub fn recv_with_ack<T, A, B, C>(
+        &mut self,
+        packet: &mut Packet,
+        ack: &mut AutoAck<T, A, B, C>,
+        timeout: Option<u32>,
+    ) -> Result<u16, Error>
+    where
+        T: timer::Instance,
+        A: PpiChannel,
+        B: PpiChannel,
+        C: PpiChannel,
+    {
+        let start = ack.now();
+
+        // clear related events
+        self.radio.events_framestart.reset();
+        self.radio.events_phyend.reset();
+        self.radio.events_end.reset();
+        packet.timestamp = None;
+
+        self.put_in_rx_mode();
+
+        // ramp up the transmitter as soon as the packet is received, to be ready to send the
+        // acknowledgment
+        self.radio
+            .shorts
+            .write(|w| w.end_disable().set_bit().disabled_txen().set_bit());
+
+        // NOTE(unsafe) DMA transfer has not yet started
+        unsafe {
+            self.radio
+                .packetptr
+                .write(|w| w.packetptr().bits(packet.buffer.as_mut_ptr() as u32));
+        }
+
+        // start transfer
+        dma_start_fence();
+        self.radio.tasks_start.write(|w| w.tasks_start().set_bit());
+
+        while self.radio.events_end.read().events_end().bit_is_clear() {
+            if let Some(timeout) = timeout {
+                if self
+                    .radio
+                    .events_framestart
+                    .read()
+                    .events_framestart()
+                    .bit_is_clear()
+                    && ack.now().wrapping_sub(start) >= timeout
+                {
+                    self.radio.shorts.reset();
+                    self.cancel_recv();
+                    return Err(Error::Timeout);
+                }
+            }
+        }
+        self.radio.events_end.reset();
+        // PHYEND is also generated at the end of the received packet
+        self.radio.events_phyend.reset();
+        // the transmitter is disabled after the acknowledgment
+        self.radio.shorts.write(|w| w.end_disable().set_bit());
+        dma_end_fence();
+
+        let timer = ack.timer.as_timer0();
+        packet.timestamp = Some(timer.cc[1].read().bits());
+        let end = timer.cc[2].read().bits();
+        let crc = self.radio.rxcrc.read().rxcrc().bits() as u16;
+        let crc_ok = self.radio.crcstatus.read().crcstatus().bit_is_set();
+
+        match ack.ack_frame(packet).filter(|_| crc_ok) {
+            Some(frame) => {
+                ack.frame.copy_from_slice(&frame);
+                // NOTE(unsafe) the transmitter is not started before the TIMER expires
+                unsafe {
+                    self.radio
+                        .packetptr
+                        .write(|w| w.packetptr().bits(ack.frame.buffer.as_ptr() as u32));
+                    // a compare value that has already passed, so the channel can be enabled
+                    // before the TIMER is armed
+                    timer.cc[0].write(|w| w.bits(end));
+                }
+                timer.events_compare[0].reset();
+                dma_start_fence();
+                ack.ack_start.enable();
+                timer.cc[0].write(|w| unsafe { w.bits(end.wrapping_add(TURNAROUND_TIME)) });
+
+                let late = ack.now().wrapping_sub(end) >= TURNAROUND_TIME
+                    && timer.events_compare[0].read().bits() == 0;
+                // the TIMER expired but the transmitter was not started, e.g. still ramping up
+                let missed = timer.events_compare[0].read().bits() != 0
+                    && self
+                        .radio
+                        .events_phyend
+                        .read()
+                        .events_phyend()
+                        .bit_is_clear()
+                    && self.radio.state.read().state().variant() != Some(STATE_A::TX);
+                if late || missed {
+                    // too late, no acknowledgment is sent
+                    ack.ack_start.disable();
+                    self.disable();
+                } else {
+                    self.wait_for_event(Event::PhyEnd);
+                    ack.ack_start.disable();
+                    self.wait_for_state_a(STATE_A::DISABLED);
+                }
+            }
+            // abort the ramp-up of the transmitter
+            None => self.disable(),
+        }
+        self.radio.shorts.reset();
+
+        if crc_ok {
+            Ok(crc)
+        } else {
+            Err(Error::Crc(crc))
+        }
+    }
+
     unsafe fn start_recv(&mut self, packet: &mut Packet) {
         // NOTE we do NOT check the address of `packet` because the mutable reference ensures it's
         // allocated in RAM
 
+        packet.timestamp = None;
+
         // clear related events
         self.radio.events_phyend.reset();
         self.radio.events_end.reset();
@@ -590,6 +791,51 @@ impl<'c> Radio<'c> {
         self.radio.shorts.reset();
     }
 
+    /// Sends the given `packet` using the unslotted CSMA-CA algorithm
+    ///
+    /// Before each CCA attempt, this method waits a random number of backoff periods (320 μs),
+    /// between 0 and 2^BE - 1, using `timer` and `rng`. BE, the backoff exponent, starts at
+    /// `config.min_be` and is incremented after each failed attempt, up to `config.max_be`. The
+    /// `ChannelAccessFailure` error is returned, and no packet is transmitted, once
+    /// `config.max_backoffs` + 1 CCA attempts have failed.
+    ///
+    /// NOTE this method will *not* modify the `packet` argument. The mutable reference is used to
+    /// ensure the `packet` buffer is allocated in RAM, which is required by the RADIO peripheral
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `config.max_be` is larger than 8, or if `config.min_be` is larger
+    /// than `config.max_be`
+    pub fn send_csma<I>(
+        &mut self,
+        packet: &mut Packet,
+        timer: &mut Timer<I>,
+        rng: &mut Rng,
+        config: CsmaConfig,
+    ) -> Result<(), ChannelAccessFailure>
+    where
+        I: timer::Instance,
+    {
+        assert!(config.max_be <= 8);
+        assert!(config.min_be <= config.max_be);
+
+        let mut be = config.min_be;
+        for _ in 0..=config.max_backoffs {
+            let backoffs = rng.random_u16() & ((1 << be) - 1);
+            if backoffs != 0 {
+                timer.delay(u32::from(backoffs) * UNIT_BACKOFF_PERIOD);
+            }
+
+            if self.try_send(packet).is_ok() {
+                return Ok(());
+            }
+
+            be = (be + 1).min(config.max_be);
+        }
+
+        Err(ChannelAccessFailure)
+    }
+
     /// Sends the specified `packet` without first performing CCA
     ///
     /// Acknowledgment packets must be sent using this method
@@ -735,6 +981,268 @@ impl<'c> Radio<'c> {
     }
 }
 
+/// aTurnaroundTime, between the end of a packet and the start of its acknowledgment, in μs
+const TURNAROUND_TIME: u32 = 192;
+
+const FRAME_TYPE_ACK: u16 = 2;
+const FRAME_TYPE_COMMAND: u16 = 3;
+const COMMAND_DATA_REQUEST: u8 = 0x04;
+const ADDRESS_MODE_NONE: u16 = 0;
+const ADDRESS_MODE_SHORT: u16 = 2;
+const ADDRESS_MODE_EXTENDED: u16 = 3;
+
+/// Connects `$event` to `$task` through a PPI or DPPI channel
+macro_rules! connect {
+    ($channel:expr, $event:expr, $task:expr) => {{
+        #[cfg(not(feature = "5340-net"))]
+        {
+            $channel.set_event_endpoint($event);
+            $channel.set_task_endpoint($task);
+        }
+        #[cfg(feature = "5340-net")]
+        {
+            $channel.publish($event);
+            $channel.subscribe($task);
+        }
+    }};
+}
+
+/// Addresses of the device, used by [`AutoAck`] to acknowledge the frames sent to it
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AutoAckConfig {
+    /// PAN identifier
+    pub pan_id: u16,
+    /// Short address, `0xFFFE` if the device has none
+    pub short_address: u16,
+    /// Extended (IEEE) address
+    pub extended_address: u64,
+    /// Whether the device is the PAN coordinator, which acknowledges the frames of its PAN that
+    /// have no destination address
+    pub pan_coordinator: bool,
+}
+
+/// Hardware acknowledgment and timestamping of received frames, see [`Radio::recv_with_ack`]
+///
+/// The TIMER runs freely at 1 MHz, and captures the time of the SFD and of the end of each
+/// received packet through two (D)PPI channels. A third channel starts the transmission of the
+/// acknowledgment on a TIMER compare event.
+pub struct AutoAck<T, A, B, C> {
+    timer: T,
+    sfd_capture: A,
+    end_capture: B,
+    ack_start: C,
+    config: AutoAckConfig,
+    frame_pending: bool,
+    frame: Packet,
+}
+
+impl<T, A, B, C> AutoAck<T, A, B, C>
+where
+    T: timer::Instance,
+    A: PpiChannel,
+    B: PpiChannel,
+    C: PpiChannel,
+{
+    /// Starts `timer` and connects the (D)PPI channels to `radio` and `timer`
+    pub fn new(
+        radio: &Radio,
+        timer: T,
+        mut sfd_capture: A,
+        mut end_capture: B,
+        mut ack_start: C,
+        config: AutoAckConfig,
+    ) -> Self {
+        timer.timer_cancel();
+        timer.disable_interrupt();
+        let regs = timer.as_timer0();
+        regs.shorts.reset();
+        regs.prescaler.write(
+            |w| unsafe { w.prescaler().bits(4) }, // 1 MHz
+        );
+        regs.bitmode.write(|w| w.bitmode()._32bit());
+        regs.tasks_clear.write(|w| unsafe { w.bits(1) });
+        regs.tasks_start.write(|w| unsafe { w.bits(1) });
+
+        connect!(
+            sfd_capture,
+            &radio.radio.events_framestart,
+            &regs.tasks_capture[1]
+        );
+        connect!(end_capture, &radio.radio.events_end, &regs.tasks_capture[2]);
+        connect!(ack_start, &regs.events_compare[0], &radio.radio.tasks_start);
+        sfd_capture.enable();
+        end_capture.enable();
+
+        Self {
+            timer,
+            sfd_capture,
+            end_capture,
+            ack_start,
+            config,
+            frame_pending: false,
+            frame: Packet::new(),
+        }
+    }
+
+    /// Changes the addresses of the device
+    pub fn set_config(&mut self, config: AutoAckConfig) {
+        self.config = config;
+    }
+
+    /// Sets the frame pending bit in the acknowledgments of Data Request commands, telling the
+    /// requesting device that data is pending for it
+    pub fn set_frame_pending(&mut self, pending: bool) {
+        self.frame_pending = pending;
+    }
+
+    /// Returns the current time of the TIMER, in μs, comparable with [`Packet::timestamp`]
+    pub fn now(&self) -> u32 {
+        let regs = self.timer.as_timer0();
+        regs.tasks_capture[3].write(|w| unsafe { w.bits(1) });
+        regs.cc[3].read().bits()
+    }
+
+    /// Stops the TIMER and disables the (D)PPI channels
+    pub fn free(mut self) -> (T, A, B, C) {
+        self.sfd_capture.disable();
+        self.end_capture.disable();
+        self.ack_start.disable();
+        self.timer.timer_cancel();
+
+        // DPPI connections are configured in the peripherals, not in the channels
+        #[cfg(feature = "5340-net")]
+        {
+            // NOTE(unsafe) only the PUBLISH and SUBSCRIBE registers set up by `new` are written
+            let radio = unsafe { &*RADIO::ptr() };
+            let regs = self.timer.as_timer0();
+            radio.events_framestart.unpublish();
+            regs.tasks_capture[1].unsubscribe();
+            radio.events_end.unpublish();
+            regs.tasks_capture[2].unsubscribe();
+            regs.events_compare[0].unpublish();
+            radio.tasks_start.unsubscribe();
+        }
+
+        (
+            self.timer,
+            self.sfd_capture,
+            self.end_capture,
+            self.ack_start,
+        )
+    }
+
+    /// Returns the acknowledgment (frame control and sequence number) of `frame`, if it requests
+    /// one and is addressed to the device
+    fn ack_frame(&self, frame: &[u8]) -> Option<[u8; 3]> {
+        let fc = u16::from_le_bytes([*frame.get(0)?, *frame.get(1)?]);
+        let sequence_number = *frame.get(2)?;
+        let frame_type = fc & 0x7;
+        let security = fc & (1 << 3) != 0;
+        let ack_request = fc & (1 << 5) != 0;
+        let pan_id_compression = fc & (1 << 6) != 0;
+        let dst_mode = (fc >> 10) & 0x3;
+        let version = (fc >> 12) & 0x3;
+        let src_mode = (fc >> 14) & 0x3;
+        if frame_type == FRAME_TYPE_ACK || !ack_request || version > 1 {
+            return None;
+        }
+
+        let read = |offset: usize, len: usize| {
+            let mut bytes = [0; 8];
+            bytes[..len].copy_from_slice(frame.get(offset..offset + len)?);
+            Some(u64::from_le_bytes(bytes))
+        };
+        let mut offset = 3;
+        match dst_mode {
+            ADDRESS_MODE_SHORT | ADDRESS_MODE_EXTENDED => {
+                let pan_id = read(offset, 2)? as u16;
+                offset += 2;
+                if pan_id != self.config.pan_id && pan_id != 0xffff {
+                    return None;
+                }
+
+                let address_match = if dst_mode == ADDRESS_MODE_SHORT {
+                    let address = read(offset, 2)? as u16;
+                    offset += 2;
+                    // broadcast frames are never acknowledged
+                    address == self.config.short_address && address < 0xfffe
+                } else {
+                    let address = read(offset, 8)?;
+                    offset += 8;
+                    address == self.config.extended_address
+                };
+                if !address_match {
+                    return None;
+                }
+            }
+            // frames without destination address are sent to the PAN coordinator
+            ADDRESS_MODE_NONE if self.config.pan_coordinator => {}
+            _ => return None,
+        }
+
+        let src_len = match src_mode {
+            ADDRESS_MODE_SHORT => 2,
+            ADDRESS_MODE_EXTENDED => 8,
+            _ => 0,
+        };
+        // the source PAN identifier is omitted if it is the destination one
+        let src_pan_id = if src_len != 0 && !(pan_id_compression && dst_mode != ADDRESS_MODE_NONE) {
+            let pan_id = read(offset, 2)? as u16;
+            offset += 2;
+            Some(pan_id)
+        } else {
+            None
+        };
+        if dst_mode == ADDRESS_MODE_NONE && src_pan_id != Some(self.config.pan_id) {
+            return None;
+        }
+        offset += src_len;
+
+        let data_request = frame_type == FRAME_TYPE_COMMAND
+            && !security
+            && frame.get(offset) == Some(&COMMAND_DATA_REQUEST);
+        let frame_pending = data_request && self.frame_pending;
+
+        Some([
+            FRAME_TYPE_ACK as u8 | u8::from(frame_pending) << 4,
+            0,
+            sequence_number,
+        ])
+    }
+}
+
+/// aUnitBackoffPeriod, the duration of a CSMA-CA backoff period, in μs
+const UNIT_BACKOFF_PERIOD: u32 = 320;
+
+/// Parameters of the unslotted CSMA-CA algorithm, see [`Radio::send_csma`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CsmaConfig {
+    /// macMinBE, the initial backoff exponent, at most `max_be`
+    pub min_be: u8,
+    /// macMaxBE, the maximum backoff exponent, at most 8
+    pub max_be: u8,
+    /// macMaxCSMABackoffs, the number of backoffs before giving up
+    pub max_backoffs: u8,
+}
+
+impl Default for CsmaConfig {
+    /// Returns the default values of the standard
+    fn default() -> Self {
+        Self {
+            min_be: 3,
+            max_be: 5,
+            max_backoffs: 4,
+        }
+    }
+}
+
+/// The channel was busy during every CCA attempt of [`Radio::send_csma`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChannelAccessFailure;
+
 /// Error
 #[derive(Copy, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -788,6 +1296,7 @@ enum Event {
 /// See figure 119 in the Product Specification of the nRF52840 for more details
 pub struct Packet {
     buffer: [u8; Self::SIZE],
+    timestamp: Option<u32>,
 }
 
 // See figure 124 in nRF52840-PS
@@ -812,6 +1321,7 @@ impl Packet {
     pub fn new() -> Self {
         let mut packet = Self {
             buffer: [0; Self::SIZE],
+            timestamp: None,
         };
         packet.set_len(0);
         packet
@@ -857,6 +1367,13 @@ impl Packet {
     pub fn lqi(&self) -> u8 {
         self.buffer[1 /* PHY_HDR */ + self.len() as usize /* data */]
     }
+
+    /// Returns the time of the SFD of the received packet, in μs
+    ///
+    /// Only recorded by [`Radio::recv_with_ack`], see [`AutoAck::now`]
+    pub fn timestamp(&self) -> Option<u32> {
+        self.timestamp
+    }
 }
 
 impl ops::Deref for Packet {
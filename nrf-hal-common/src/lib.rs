@@ -7,6 +7,9 @@
 #[cfg(feature = "rtic-monotonic")]
 pub mod monotonic;
 
+#[cfg(any(feature = "embedded-hal-async", feature = "embedded-io-async"))]
+mod asynch;
+
 #[cfg(feature = "51")]
 pub use nrf51_pac as pac;
 
@@ -39,6 +42,13 @@ pub use nrf9160_pac as pac;
 
 #[cfg(feature = "51")]
 pub mod adc;
+#[cfg(not(any(
+    feature = "51",
+    feature = "9160",
+    feature = "5340-app",
+    feature = "5340-net"
+)))]
+pub mod buffered_uarte;
 #[cfg(not(any(feature = "9160", feature = "5340-app")))]
 pub mod ccm;
 pub mod clocks;
@@ -52,8 +62,13 @@ pub mod clocks;
 pub mod comp;
 #[cfg(not(feature = "51"))]
 pub mod delay;
+#[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+pub mod dppi;
 #[cfg(not(any(feature = "9160", feature = "5340-app")))]
 pub mod ecb;
+#[cfg(not(feature = "51"))]
+pub mod egu;
+pub mod ficr;
 pub mod gpio;
 #[cfg(not(feature = "5340-app"))]
 pub mod gpiote;
@@ -65,7 +80,12 @@ pub mod gpiote;
     feature = "5340-net"
 )))]
 pub mod i2s;
-#[cfg(any(feature = "52833", feature = "52840"))]
+#[cfg(any(
+    feature = "52811",
+    feature = "52833",
+    feature = "52840",
+    feature = "5340-net"
+))]
 pub mod ieee802154;
 #[cfg(not(any(
     feature = "52805",
@@ -76,8 +96,14 @@ pub mod ieee802154;
     feature = "5340-net"
 )))]
 pub mod lpcomp;
+#[cfg(any(feature = "52832", feature = "52833", feature = "52840"))]
+pub mod nfct;
 #[cfg(not(feature = "51"))]
 pub mod nvmc;
+#[cfg(any(feature = "52833", feature = "52840", feature = "5340-app"))]
+pub mod pdm;
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
+pub mod power;
 #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
 pub mod ppi;
 #[cfg(not(any(feature = "51", feature = "52805", feature = "5340-net")))]
@@ -89,6 +115,15 @@ pub mod pwm;
     feature = "5340-net"
 )))]
 pub mod qdec;
+#[cfg(any(feature = "52840", feature = "5340-app"))]
+pub mod qspi;
+#[cfg(not(any(
+    feature = "51",
+    feature = "9160",
+    feature = "5340-app",
+    feature = "5340-net"
+)))]
+pub mod radio;
 #[cfg(not(any(feature = "9160", feature = "5340-app")))]
 pub mod rng;
 pub mod rtc;
@@ -114,13 +149,14 @@ pub mod twis;
 pub mod uart;
 #[cfg(not(feature = "51"))]
 pub mod uarte;
-#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
 pub mod uicr;
 #[cfg(feature = "nrf-usbd")]
 pub mod usbd;
 pub mod wdt;
 
 pub mod prelude {
+    #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+    pub use crate::dppi::{Dppi, Publish, Subscribe};
     #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
     pub use crate::ppi::{ConfigurablePpi, Ppi};
     pub use crate::time::U32Ext;
@@ -0,0 +1,486 @@
+//! HAL interface to the NFCT peripheral.
+//!
+//! The NFCT peripheral is an NFC-A listener (tag) frontend. It detects the field of a reader,
+//! handles the anticollision and selection procedure in hardware using the configured NFCID1, and
+//! exchanges frames with the reader through EasyDMA.
+//!
+//! The antenna is connected to the NFC1 and NFC2 pins (P0.09 and P0.10), which must be configured
+//! for NFC in `UICR.NFCPINS`. The high frequency crystal oscillator must be running while the
+//! peripheral is activated, see
+//! [`Clocks::enable_ext_hfosc`](crate::clocks::Clocks::enable_ext_hfosc).
+//!
+//! The [`type2`] module emulates an NFC Forum Type 2 Tag holding an NDEF message on top of this
+//! driver.
+
+use core::sync::atomic::{compiler_fence, Ordering};
+
+use crate::pac::NFCT;
+use crate::slice_in_ram;
+
+pub mod type2;
+
+/// Largest frame handled by EasyDMA, in bytes.
+pub const MAX_FRAME_LEN: usize = 257;
+
+/// Interface to the NFCT peripheral.
+pub struct Nfct {
+    nfct: NFCT,
+}
+
+impl Nfct {
+    /// Takes ownership of the NFCT peripheral and configures it.
+    ///
+    /// The peripheral is left disabled, call [`sense`](Self::sense) to start detecting a field.
+    pub fn new(nfct: NFCT, config: &Config) -> Self {
+        let nfct = Self { nfct };
+        nfct.disable();
+        nfct.set_nfcid1(&config.nfcid1);
+        // The CASCADE bit is handled by the hardware.
+        nfct.nfct
+            .selres
+            .write(|w| unsafe { w.protocol().bits(config.protocol as u8) });
+
+        #[cfg(any(feature = "52833", feature = "52840"))]
+        nfct.nfct.autocolresconfig.write(|w| {
+            if config.auto_collision_resolution {
+                w.mode().enabled()
+            } else {
+                w.mode().disabled()
+            }
+        });
+
+        // Respond in the first slot of the grid after the minimum frame delay.
+        nfct.nfct
+            .framedelaymode
+            .write(|w| w.framedelaymode().window_grid());
+        nfct.nfct
+            .framedelaymax
+            .write(|w| unsafe { w.framedelaymax().bits(config.frame_delay_max.into()) });
+
+        nfct.nfct.rxd.frameconfig.write(|w| {
+            w.parity().parity();
+            w.sof().sof();
+            w.crcmoderx().crc16rx();
+            w
+        });
+        nfct
+    }
+
+    /// Enters the SENSE state, where the peripheral only detects the field of a reader, with a
+    /// low current consumption.
+    ///
+    /// Unless [`set_auto_activate`](Self::set_auto_activate) is used, the peripheral has to be
+    /// activated with [`activate`](Self::activate) once the `FieldDetected` event is triggered.
+    pub fn sense(&mut self) {
+        self.reset_event(NfctEvent::FieldDetected);
+        self.reset_event(NfctEvent::FieldLost);
+        self.nfct.tasks_sense.write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Activates the peripheral, which then answers to the reader and takes part in the
+    /// anticollision procedure.
+    ///
+    /// The `Selected` event is triggered when the tag has been selected by the reader.
+    pub fn activate(&mut self) {
+        self.reset_event(NfctEvent::Selected);
+        self.reset_event(NfctEvent::FieldLost);
+        self.nfct.tasks_activate.write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Disables the peripheral, neither detecting the field nor answering to the reader.
+    pub fn disable(&self) {
+        self.nfct.tasks_disable.write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Moves the peripheral to the IDLE state, where it waits for a `REQA` or `WUPA` command.
+    pub fn go_idle(&mut self) {
+        self.nfct.tasks_goidle.write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Moves the peripheral to the SLEEP_A state, where it only answers to a `WUPA` command.
+    ///
+    /// This is the state required after receiving a `HLTA` command.
+    pub fn go_sleep(&mut self) {
+        self.nfct.tasks_gosleep.write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Activates the peripheral as soon as a field is detected, and moves it back to the SENSE
+    /// state when the field is lost.
+    pub fn set_auto_activate(&mut self, enabled: bool) {
+        self.nfct.shorts.modify(|_, w| {
+            w.fielddetected_activate().bit(enabled);
+            w.fieldlost_sense().bit(enabled);
+            w
+        });
+    }
+
+    /// Returns `true` if the field of a reader is present and the peripheral is locked to it.
+    pub fn is_field_present(&self) -> bool {
+        let r = self.nfct.fieldpresent.read();
+        r.fieldpresent().is_field_present() && r.lockdetect().is_locked()
+    }
+
+    /// Returns the state of the tag.
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    pub fn state(&self) -> TagState {
+        let r = self.nfct.nfctagstate.read();
+        let state = r.nfctagstate();
+        if state.is_disabled() {
+            TagState::Disabled
+        } else if state.is_ramp_up() {
+            TagState::RampUp
+        } else if state.is_idle() {
+            TagState::Idle
+        } else if state.is_receive() {
+            TagState::Receive
+        } else if state.is_frame_delay() {
+            TagState::FrameDelay
+        } else {
+            TagState::Transmit
+        }
+    }
+
+    /// Waits for the tag to be selected by a reader.
+    ///
+    /// The peripheral must have been activated, directly or with
+    /// [`set_auto_activate`](Self::set_auto_activate).
+    pub fn wait_selected(&mut self) -> Result<(), Error> {
+        self.wait_event(NfctEvent::Selected)?;
+        self.reset_event(NfctEvent::Selected);
+        Ok(())
+    }
+
+    /// Receives a frame from the reader into `buf`, returning the number of bytes received.
+    ///
+    /// The CRC of the frame is checked and removed. Blocks until a frame is received, or returns
+    /// [`Error::FieldLost`] if the field is lost meanwhile.
+    pub fn receive(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        if buf.len() > MAX_FRAME_LEN {
+            return Err(Error::BufferTooLong);
+        }
+
+        self.nfct
+            .packetptr
+            .write(|w| unsafe { w.bits(buf.as_mut_ptr() as u32) });
+        self.nfct
+            .maxlen
+            .write(|w| unsafe { w.bits(buf.len() as u32) });
+        self.reset_event(NfctEvent::RxFrameEnd);
+        self.reset_event(NfctEvent::RxError);
+
+        // Conservative compiler fence to prevent optimizations that do not
+        // take in to account actions by DMA. The fence has been placed here,
+        // before any DMA action has started.
+        compiler_fence(Ordering::SeqCst);
+        self.nfct.tasks_enablerxdata.write(|w| unsafe { w.bits(1) });
+        let result = self.wait_event(NfctEvent::RxFrameEnd);
+        // Conservative compiler fence to prevent optimizations that do not
+        // take in to account actions by DMA. The fence has been placed here,
+        // after all possible DMA actions have completed.
+        compiler_fence(Ordering::SeqCst);
+        result?;
+        self.reset_event(NfctEvent::RxFrameEnd);
+
+        // The status bits are cleared by writing 1 to them.
+        let status = self.nfct.framestatus.rx.read();
+        self.nfct
+            .framestatus
+            .rx
+            .write(|w| unsafe { w.bits(status.bits()) });
+        if status.overrun().is_overrun() {
+            return Err(Error::Overrun);
+        }
+        if status.paritystatus().is_parity_error() {
+            return Err(Error::Parity);
+        }
+        if status.crcerror().is_crcerror() {
+            return Err(Error::Crc);
+        }
+
+        let len = usize::from(self.nfct.rxd.amount.read().rxdatabytes().bits());
+        Ok(len.min(buf.len()))
+    }
+
+    /// Transmits `frame` to the reader, followed by its CRC.
+    ///
+    /// The frame is sent in the frame delay window following the last received frame, so it has
+    /// to be started before that window expires, see [`Config::frame_delay_max`].
+    pub fn transmit(&mut self, frame: &[u8]) -> Result<(), Error> {
+        if frame.len() > MAX_FRAME_LEN {
+            return Err(Error::BufferTooLong);
+        }
+        if !slice_in_ram(frame) {
+            return Err(Error::DMABufferNotInDataMemory);
+        }
+        self.start_tx(frame, frame.len() as u16, 0, true)
+    }
+
+    /// Transmits a short frame made of the `bits` (1 to 7) lower bits of `value`, without CRC.
+    ///
+    /// This is used for the 4 bit ACK and NAK responses of Type 2 Tags.
+    pub fn transmit_bits(&mut self, value: u8, bits: u8) -> Result<(), Error> {
+        assert!(bits > 0 && bits < 8);
+        let buf = [value];
+        self.start_tx(&buf, 0, bits, false)
+    }
+
+    /// Checks if an event has been triggered.
+    #[inline(always)]
+    pub fn is_event_triggered(&self, event: NfctEvent) -> bool {
+        match event {
+            NfctEvent::Ready => self.nfct.events_ready.read().bits() != 0,
+            NfctEvent::FieldDetected => self.nfct.events_fielddetected.read().bits() != 0,
+            NfctEvent::FieldLost => self.nfct.events_fieldlost.read().bits() != 0,
+            NfctEvent::TxFrameEnd => self.nfct.events_txframeend.read().bits() != 0,
+            NfctEvent::RxFrameEnd => self.nfct.events_rxframeend.read().bits() != 0,
+            NfctEvent::Error => self.nfct.events_error.read().bits() != 0,
+            NfctEvent::RxError => self.nfct.events_rxerror.read().bits() != 0,
+            NfctEvent::Collision => self.nfct.events_collision.read().bits() != 0,
+            NfctEvent::Selected => self.nfct.events_selected.read().bits() != 0,
+        }
+    }
+
+    /// Marks event as handled.
+    #[inline(always)]
+    pub fn reset_event(&self, event: NfctEvent) {
+        match event {
+            NfctEvent::Ready => self.nfct.events_ready.reset(),
+            NfctEvent::FieldDetected => self.nfct.events_fielddetected.reset(),
+            NfctEvent::FieldLost => self.nfct.events_fieldlost.reset(),
+            NfctEvent::TxFrameEnd => self.nfct.events_txframeend.reset(),
+            NfctEvent::RxFrameEnd => self.nfct.events_rxframeend.reset(),
+            NfctEvent::Error => self.nfct.events_error.reset(),
+            NfctEvent::RxError => self.nfct.events_rxerror.reset(),
+            NfctEvent::Collision => self.nfct.events_collision.reset(),
+            NfctEvent::Selected => self.nfct.events_selected.reset(),
+        }
+    }
+
+    /// Enables interrupt triggering on the specified event.
+    #[inline(always)]
+    pub fn enable_interrupt(&self, event: NfctEvent) -> &Self {
+        self.nfct
+            .intenset
+            .write(|w| unsafe { w.bits(event.inten_mask()) });
+        self
+    }
+
+    /// Disables interrupt triggering on the specified event.
+    #[inline(always)]
+    pub fn disable_interrupt(&self, event: NfctEvent) -> &Self {
+        self.nfct
+            .intenclr
+            .write(|w| unsafe { w.bits(event.inten_mask()) });
+        self
+    }
+
+    /// Disables the peripheral and returns the raw peripheral.
+    pub fn free(self) -> NFCT {
+        self.disable();
+        self.nfct.shorts.reset();
+        self.nfct.intenclr.write(|w| unsafe { w.bits(0xffff_ffff) });
+        self.nfct
+    }
+
+    fn set_nfcid1(&self, nfcid1: &Nfcid1) {
+        let id = nfcid1.as_bytes();
+        // The last 4 bytes of the ID go in NFCID1_LAST, the 3 bytes before in NFCID1_2ND_LAST and
+        // the first 3 bytes of a triple size ID in NFCID1_3RD_LAST. The first byte of each part
+        // is the most significant one.
+        let (rest, last) = id.split_at(id.len() - 4);
+        self.nfct
+            .nfcid1_last
+            .write(|w| unsafe { w.bits(be_bits(last)) });
+        let (first, second) = rest.split_at(rest.len().saturating_sub(3));
+        self.nfct
+            .nfcid1_2nd_last
+            .write(|w| unsafe { w.bits(be_bits(second)) });
+        self.nfct
+            .nfcid1_3rd_last
+            .write(|w| unsafe { w.bits(be_bits(first)) });
+
+        self.nfct.sensres.write(|w| {
+            w.bitframesdd().sdd00001();
+            match nfcid1 {
+                Nfcid1::Single(_) => w.nfcidsize().nfcid1single(),
+                Nfcid1::Double(_) => w.nfcidsize().nfcid1double(),
+                Nfcid1::Triple(_) => w.nfcidsize().nfcid1triple(),
+            };
+            w
+        });
+    }
+
+    /// Transmits the first `bytes` bytes of `buf` followed by `bits` bits of the next byte, with a
+    /// CRC if `crc` is set.
+    fn start_tx(&mut self, buf: &[u8], bytes: u16, bits: u8, crc: bool) -> Result<(), Error> {
+        self.nfct
+            .packetptr
+            .write(|w| unsafe { w.bits(buf.as_ptr() as u32) });
+        self.nfct
+            .maxlen
+            .write(|w| unsafe { w.bits(buf.len() as u32) });
+        self.nfct.txd.amount.write(|w| unsafe {
+            w.txdatabytes().bits(bytes);
+            w.txdatabits().bits(bits);
+            w
+        });
+        self.nfct.txd.frameconfig.write(|w| {
+            w.parity().parity();
+            w.discardmode().discard_start();
+            w.sof().sof();
+            if crc {
+                w.crcmodetx().crc16tx();
+            } else {
+                w.crcmodetx().no_crctx();
+            }
+            w
+        });
+        self.reset_event(NfctEvent::TxFrameEnd);
+
+        compiler_fence(Ordering::SeqCst);
+        self.nfct.tasks_starttx.write(|w| unsafe { w.bits(1) });
+        let result = self.wait_event(NfctEvent::TxFrameEnd);
+        compiler_fence(Ordering::SeqCst);
+        result?;
+        self.reset_event(NfctEvent::TxFrameEnd);
+        Ok(())
+    }
+
+    /// Waits for `event`, or returns an error if the field is lost first.
+    fn wait_event(&self, event: NfctEvent) -> Result<(), Error> {
+        loop {
+            if self.is_event_triggered(event) {
+                return Ok(());
+            }
+            if self.is_event_triggered(NfctEvent::FieldLost) {
+                return Err(Error::FieldLost);
+            }
+        }
+    }
+}
+
+/// Packs up to 4 bytes into a register value, the first byte being the most significant one.
+fn be_bits(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(0, |bits, byte| bits << 8 | u32::from(*byte))
+}
+
+/// NFCID1 of the tag, sent to the reader during anticollision.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Nfcid1 {
+    Single([u8; 4]),
+    Double([u8; 7]),
+    Triple([u8; 10]),
+}
+
+impl Nfcid1 {
+    /// Returns the bytes of the ID, in the order they are sent.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Nfcid1::Single(id) => id,
+            Nfcid1::Double(id) => id,
+            Nfcid1::Triple(id) => id,
+        }
+    }
+}
+
+/// Protocol advertised in the `SEL_RES` response.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Protocol {
+    /// NFC Forum Type 2 Tag platform.
+    Type2Tag = 0,
+    /// NFC Forum Type 4A Tag platform (ISO-DEP).
+    Type4Tag = 1,
+}
+
+/// Configuration of the NFCT peripheral.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// NFCID1 of the tag.
+    pub nfcid1: Nfcid1,
+    /// Protocol advertised to the reader.
+    pub protocol: Protocol,
+    /// End of the window in which responses can be sent after a received frame, in units of
+    /// 1/13.56 MHz.
+    pub frame_delay_max: u16,
+    /// Let the hardware answer to the anticollision procedure (the default). When disabled, the
+    /// application has to handle it.
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    pub auto_collision_resolution: bool,
+}
+
+impl Config {
+    /// Returns a configuration for a Type 2 Tag with the given 7 byte ID.
+    pub fn type2(nfcid1: [u8; 7]) -> Self {
+        Self {
+            nfcid1: Nfcid1::Double(nfcid1),
+            protocol: Protocol::Type2Tag,
+            frame_delay_max: 0xffff,
+            #[cfg(any(feature = "52833", feature = "52840"))]
+            auto_collision_resolution: true,
+        }
+    }
+}
+
+/// State of the tag.
+#[cfg(any(feature = "52833", feature = "52840"))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TagState {
+    Disabled,
+    RampUp,
+    Idle,
+    Receive,
+    FrameDelay,
+    Transmit,
+}
+
+/// NFCT events
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum NfctEvent {
+    Ready,
+    FieldDetected,
+    FieldLost,
+    TxFrameEnd,
+    RxFrameEnd,
+    Error,
+    RxError,
+    Collision,
+    Selected,
+}
+
+impl NfctEvent {
+    fn inten_mask(self) -> u32 {
+        match self {
+            NfctEvent::Ready => 1 << 0,
+            NfctEvent::FieldDetected => 1 << 1,
+            NfctEvent::FieldLost => 1 << 2,
+            NfctEvent::TxFrameEnd => 1 << 4,
+            NfctEvent::RxFrameEnd => 1 << 6,
+            NfctEvent::Error => 1 << 7,
+            NfctEvent::RxError => 1 << 10,
+            NfctEvent::Collision => 1 << 18,
+            NfctEvent::Selected => 1 << 19,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// EasyDMA can only read from data memory, read only buffers in flash will fail.
+    DMABufferNotInDataMemory,
+    /// The buffer is longer than [`MAX_FRAME_LEN`], or the NDEF message does not fit in the tag.
+    BufferTooLong,
+    /// The field of the reader was lost.
+    FieldLost,
+    /// The received frame has a CRC error.
+    Crc,
+    /// The received frame has a parity error.
+    Parity,
+    /// The received frame was not completely written to RAM.
+    Overrun,
+}
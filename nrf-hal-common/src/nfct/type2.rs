@@ -0,0 +1,435 @@
+//! NFC Forum Type 2 Tag emulation.
+//!
+//! [`Type2Tag`] exposes a memory area as the memory of a Type 2 Tag, and answers the `READ`,
+//! `WRITE` and `HLTA` commands of a reader. The data area holds an NDEF message in an NDEF TLV, so
+//! a phone tapping the device reads the message, and can write a new one for the application to
+//! pick up with [`Type2Tag::ndef`].
+//!
+//! ```ignore
+//! let mut nfct = Nfct::new(p.NFCT, &nfct::Config::type2(uid));
+//! let mut tag = Type2Tag::new(unsafe { &mut MEMORY }, uid);
+//! tag.set_ndef_record(Tnf::WellKnown, b"U", b"\x04example.com")?;
+//!
+//! nfct.set_auto_activate(true);
+//! nfct.sense();
+//! loop {
+//!     if tag.serve(&mut nfct)? {
+//!         // The reader wrote to the tag.
+//!         if let Some(message) = tag.ndef() { /* ... */ }
+//!     }
+//! }
+//! ```
+
+use super::{Error, Nfct};
+
+/// Size of a block, the unit of the `WRITE` command.
+pub const BLOCK_SIZE: usize = 4;
+
+/// Size of the header (UID, lock bytes and capability container) at the start of the memory.
+pub const HEADER_SIZE: usize = 4 * BLOCK_SIZE;
+
+const CMD_READ: u8 = 0x30;
+const CMD_WRITE: u8 = 0xa2;
+const CMD_HALT: u8 = 0x50;
+
+const ACK: u8 = 0xa;
+const NAK_INVALID_ARGUMENT: u8 = 0x0;
+
+const TLV_NULL: u8 = 0x00;
+const TLV_NDEF: u8 = 0x03;
+const TLV_TERMINATOR: u8 = 0xfe;
+
+/// Offset of the access conditions in the capability container.
+const CC_ACCESS: usize = 15;
+
+/// An emulated Type 2 Tag.
+pub struct Type2Tag<'a> {
+    memory: &'a mut [u8],
+}
+
+impl<'a> Type2Tag<'a> {
+    /// Creates a tag with an empty NDEF message, using `memory` as the tag memory.
+    ///
+    /// `memory` holds the 16 byte header followed by the data area, whose size must be a multiple
+    /// of 8 bytes, from 8 to 2040 bytes. `uid` must be the NFCID1 the NFCT peripheral is
+    /// configured with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the data area is not a multiple of 8 bytes, from 8 to 2040 bytes.
+    pub fn new(memory: &'a mut [u8], uid: [u8; 7]) -> Self {
+        let data_size = memory.len().saturating_sub(HEADER_SIZE);
+        assert!(data_size >= 8 && data_size <= 2040 && data_size % 8 == 0);
+
+        memory.iter_mut().for_each(|byte| *byte = 0);
+        // UID with its check bytes, the first one including the cascade tag (0x88). The
+        // following internal byte and lock bytes stay 0.
+        memory[..3].copy_from_slice(&uid[..3]);
+        memory[3] = 0x88 ^ uid[0] ^ uid[1] ^ uid[2];
+        memory[4..8].copy_from_slice(&uid[3..]);
+        memory[8] = uid[3] ^ uid[4] ^ uid[5] ^ uid[6];
+        // Capability container: NDEF magic number, version 1.0, data area size in units of 8
+        // bytes, read and write access.
+        memory[12..16].copy_from_slice(&[0xe1, 0x10, (data_size / 8) as u8, 0x00]);
+        // Empty NDEF message.
+        memory[16..19].copy_from_slice(&[TLV_NDEF, 0x00, TLV_TERMINATOR]);
+
+        Self { memory }
+    }
+
+    /// Returns the whole tag memory.
+    pub fn memory(&self) -> &[u8] {
+        self.memory
+    }
+
+    /// Returns the NDEF message stored in the tag, or `None` if the data area does not contain a
+    /// valid NDEF TLV.
+    pub fn ndef(&self) -> Option<&[u8]> {
+        let data = &self.memory[HEADER_SIZE..];
+        let mut offset = 0;
+        loop {
+            match *data.get(offset)? {
+                TLV_NULL => offset += 1,
+                TLV_TERMINATOR => return None,
+                tag => {
+                    let (len, value) = match *data.get(offset + 1)? {
+                        0xff => {
+                            let len = data.get(offset + 2..offset + 4)?;
+                            (
+                                usize::from(u16::from_be_bytes([len[0], len[1]])),
+                                offset + 4,
+                            )
+                        }
+                        len => (usize::from(len), offset + 2),
+                    };
+                    if tag == TLV_NDEF {
+                        return data.get(value..value + len);
+                    }
+                    offset = value + len;
+                }
+            }
+        }
+    }
+
+    /// Stores `message`, an encoded NDEF message, in the tag.
+    pub fn set_ndef(&mut self, message: &[u8]) -> Result<(), Error> {
+        self.write_ndef_tlv(message.len(), |value| value.copy_from_slice(message))
+    }
+
+    /// Stores an NDEF message made of a single record in the tag.
+    ///
+    /// For instance, a URI record for `https://example.com` has the `WellKnown` TNF, the `b"U"`
+    /// type and the `b"\x04example.com"` payload.
+    pub fn set_ndef_record(
+        &mut self,
+        tnf: Tnf,
+        record_type: &[u8],
+        payload: &[u8],
+    ) -> Result<(), Error> {
+        if record_type.len() > 0xff {
+            return Err(Error::BufferTooLong);
+        }
+        let short = payload.len() <= 0xff;
+        let header_len = if short { 3 } else { 6 };
+        let len = header_len + record_type.len() + payload.len();
+
+        self.write_ndef_tlv(len, |record| {
+            // Message Begin, Message End, Short Record.
+            record[0] = 0x80 | 0x40 | (u8::from(short) << 4) | tnf as u8;
+            record[1] = record_type.len() as u8;
+            if short {
+                record[2] = payload.len() as u8;
+            } else {
+                record[2..6].copy_from_slice(&(payload.len() as u32).to_be_bytes());
+            }
+            let (ty, rest) = record[header_len..].split_at_mut(record_type.len());
+            ty.copy_from_slice(record_type);
+            rest.copy_from_slice(payload);
+        })
+    }
+
+    /// Makes the tag read-only for the reader, or writeable again.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.memory[CC_ACCESS] = if read_only { 0x0f } else { 0x00 };
+    }
+
+    /// Waits for the tag to be selected by a reader, and answers its commands until it halts the
+    /// tag or the field is lost.
+    ///
+    /// Returns `true` if the reader wrote to the tag during the session. The peripheral must have
+    /// been activated, which is best done with [`Nfct::set_auto_activate`], and this function has
+    /// to be called again for every session.
+    ///
+    /// The lock bytes are stored, but not enforced: only the access conditions of the capability
+    /// container (see [`set_read_only`](Self::set_read_only)) protect the data area.
+    pub fn serve(&mut self, nfct: &mut Nfct) -> Result<bool, Error> {
+        let mut written = false;
+        match self.session(nfct, &mut written) {
+            Ok(()) | Err(Error::FieldLost) => Ok(written),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn session(&mut self, nfct: &mut Nfct, written: &mut bool) -> Result<(), Error> {
+        nfct.wait_selected()?;
+
+        let mut command = [0; 16];
+        let mut response = [0; 16];
+        loop {
+            let len = match nfct.receive(&mut command) {
+                Ok(len) => len,
+                // Corrupted frames are not answered.
+                Err(Error::Crc) | Err(Error::Parity) | Err(Error::Overrun) => continue,
+                Err(e) => return Err(e),
+            };
+
+            match command[..len] {
+                [CMD_READ, block] if self.read(block, &mut response) => {
+                    nfct.transmit(&response)?;
+                }
+                [CMD_WRITE, block, d0, d1, d2, d3] if self.write(block, [d0, d1, d2, d3]) => {
+                    *written = true;
+                    nfct.transmit_bits(ACK, 4)?;
+                }
+                [CMD_HALT, 0x00] => {
+                    nfct.go_sleep();
+                    return Ok(());
+                }
+                _ => {
+                    // The tag goes back to the IDLE state after a NAK.
+                    nfct.transmit_bits(NAK_INVALID_ARGUMENT, 4)?;
+                    nfct.go_idle();
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Reads 4 blocks starting at `block`, rolling over to block 0 at the end of the memory.
+    fn read(&self, block: u8, response: &mut [u8; 16]) -> bool {
+        let start = usize::from(block) * BLOCK_SIZE;
+        if start >= self.memory.len() {
+            return false;
+        }
+        for (i, byte) in response.iter_mut().enumerate() {
+            *byte = self.memory[(start + i) % self.memory.len()];
+        }
+        true
+    }
+
+    fn write(&mut self, block: u8, data: [u8; BLOCK_SIZE]) -> bool {
+        let start = usize::from(block) * BLOCK_SIZE;
+        if start >= self.memory.len() {
+            return false;
+        }
+        let read_only = self.memory[CC_ACCESS] != 0x00;
+        let target = &mut self.memory[start..start + BLOCK_SIZE];
+        match block {
+            // The UID can not be written.
+            0 | 1 => return false,
+            // Only the lock bytes of block 2 can be written, and the lock bits can only be set.
+            2 => {
+                target[2] |= data[2];
+                target[3] |= data[3];
+            }
+            // The capability container is one time programmable.
+            3 => target.iter_mut().zip(&data).for_each(|(t, d)| *t |= d),
+            _ if read_only => return false,
+            _ => target.copy_from_slice(&data),
+        }
+        true
+    }
+
+    /// Writes an NDEF TLV of `len` bytes, filled by `fill`, followed by a terminator TLV.
+    fn write_ndef_tlv(&mut self, len: usize, fill: impl FnOnce(&mut [u8])) -> Result<(), Error> {
+        let data = &mut self.memory[HEADER_SIZE..];
+        let header_len = if len < 0xff { 2 } else { 4 };
+        if len > 0xfffe || header_len + len + 1 > data.len() {
+            return Err(Error::BufferTooLong);
+        }
+
+        data[0] = TLV_NDEF;
+        if len < 0xff {
+            data[1] = len as u8;
+        } else {
+            data[1] = 0xff;
+            data[2..4].copy_from_slice(&(len as u16).to_be_bytes());
+        }
+        fill(&mut data[header_len..header_len + len]);
+        data[header_len + len] = TLV_TERMINATOR;
+        data[header_len + len + 1..]
+            .iter_mut()
+            .for_each(|byte| *byte = 0);
+        Ok(())
+    }
+}
+
+/// Type Name Format of an NDEF record.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Tnf {
+    Empty = 0,
+    /// NFC Forum well-known type, like `U` (URI) or `T` (text).
+    WellKnown = 1,
+    /// Media type, like `application/json`.
+    Media = 2,
+    AbsoluteUri = 3,
+    /// NFC Forum external type, like `example.com:config`.
+    External = 4,
+    Unknown = 5,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UID: [u8; 7] = [0x04, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+
+    #[test]
+    fn new_formats_memory() {
+        let mut memory = [0xff; HEADER_SIZE + 48];
+        let tag = Type2Tag::new(&mut memory, UID);
+
+        let memory = tag.memory();
+        assert_eq!(memory[..4], [0x04, 0x01, 0x02, 0x88 ^ 0x04 ^ 0x01 ^ 0x02]);
+        assert_eq!(
+            memory[4..9],
+            [0x03, 0x04, 0x05, 0x06, 0x03 ^ 0x04 ^ 0x05 ^ 0x06]
+        );
+        assert_eq!(memory[12..16], [0xe1, 0x10, 6, 0x00]);
+        assert_eq!(memory[16..19], [TLV_NDEF, 0x00, TLV_TERMINATOR]);
+        assert!(memory[19..].iter().all(|byte| *byte == 0));
+        assert_eq!(tag.ndef(), Some(&[][..]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_rejects_unaligned_data_area() {
+        let mut memory = [0; HEADER_SIZE + 12];
+        Type2Tag::new(&mut memory, UID);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_rejects_missing_data_area() {
+        let mut memory = [0; HEADER_SIZE];
+        Type2Tag::new(&mut memory, UID);
+    }
+
+    #[test]
+    fn set_ndef_round_trips() {
+        let mut memory = [0; HEADER_SIZE + 48];
+        let mut tag = Type2Tag::new(&mut memory, UID);
+
+        tag.set_ndef(&[1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(tag.ndef(), Some(&[1, 2, 3, 4, 5][..]));
+        let data = &tag.memory()[HEADER_SIZE..];
+        assert_eq!(data[..8], [TLV_NDEF, 5, 1, 2, 3, 4, 5, TLV_TERMINATOR]);
+
+        // A shorter message clears the rest of the previous one.
+        tag.set_ndef(&[6]).unwrap();
+        assert_eq!(tag.ndef(), Some(&[6][..]));
+        let data = &tag.memory()[HEADER_SIZE..];
+        assert_eq!(data[..3], [TLV_NDEF, 1, 6]);
+        assert_eq!(data[3], TLV_TERMINATOR);
+        assert!(data[4..].iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn set_ndef_uses_three_byte_length_format() {
+        let mut memory = [0; HEADER_SIZE + 264];
+        let mut tag = Type2Tag::new(&mut memory, UID);
+        let message = [0x5a; 0xff];
+
+        tag.set_ndef(&message).unwrap();
+        assert_eq!(tag.ndef(), Some(&message[..]));
+        let data = &tag.memory()[HEADER_SIZE..];
+        assert_eq!(data[..4], [TLV_NDEF, 0xff, 0x00, 0xff]);
+        assert_eq!(data[4 + 0xff], TLV_TERMINATOR);
+    }
+
+    #[test]
+    fn set_ndef_rejects_messages_too_long() {
+        let mut memory = [0; HEADER_SIZE + 8];
+        let mut tag = Type2Tag::new(&mut memory, UID);
+
+        // 2 bytes of TLV header and the terminator leave 5 bytes for the message.
+        assert_eq!(tag.set_ndef(&[0; 6]), Err(Error::BufferTooLong));
+        tag.set_ndef(&[0; 5]).unwrap();
+    }
+
+    #[test]
+    fn set_ndef_record_short() {
+        let mut memory = [0; HEADER_SIZE + 48];
+        let mut tag = Type2Tag::new(&mut memory, UID);
+
+        tag.set_ndef_record(Tnf::WellKnown, b"U", b"\x04example.com")
+            .unwrap();
+        let record = tag.ndef().unwrap();
+        assert_eq!(record[..4], [0xd1, 1, 12, b'U']);
+        assert_eq!(&record[4..], b"\x04example.com");
+    }
+
+    #[test]
+    fn set_ndef_record_long() {
+        let mut memory = [0; HEADER_SIZE + 288];
+        let mut tag = Type2Tag::new(&mut memory, UID);
+        let payload = [0xa5; 0x100];
+
+        tag.set_ndef_record(Tnf::Media, b"a/b", &payload).unwrap();
+        let record = tag.ndef().unwrap();
+        assert_eq!(record[..9], [0xc2, 3, 0, 0, 1, 0, b'a', b'/', b'b']);
+        assert_eq!(record[9..], payload[..]);
+    }
+
+    #[test]
+    fn set_ndef_record_rejects_long_types() {
+        let mut memory = [0; HEADER_SIZE + 512];
+        let mut tag = Type2Tag::new(&mut memory, UID);
+
+        assert_eq!(
+            tag.set_ndef_record(Tnf::External, &[b'x'; 0x100], &[]),
+            Err(Error::BufferTooLong)
+        );
+    }
+
+    #[test]
+    fn ndef_skips_other_tlvs() {
+        let mut memory = [0; HEADER_SIZE + 16];
+        let mut tag = Type2Tag::new(&mut memory, UID);
+
+        // NULL TLV, Lock Control TLV, NDEF TLV.
+        tag.memory[HEADER_SIZE..HEADER_SIZE + 11].copy_from_slice(&[
+            TLV_NULL,
+            0x01,
+            0x03,
+            0xa0,
+            0x10,
+            0x44,
+            TLV_NDEF,
+            0x02,
+            0xaa,
+            0xbb,
+            TLV_TERMINATOR,
+        ]);
+        assert_eq!(tag.ndef(), Some(&[0xaa, 0xbb][..]));
+    }
+
+    #[test]
+    fn ndef_rejects_invalid_tlvs() {
+        let mut memory = [0; HEADER_SIZE + 8];
+        let mut tag = Type2Tag::new(&mut memory, UID);
+
+        // Terminator before any NDEF TLV.
+        tag.memory[HEADER_SIZE] = TLV_TERMINATOR;
+        assert_eq!(tag.ndef(), None);
+
+        // NDEF TLV longer than the data area.
+        tag.memory[HEADER_SIZE..HEADER_SIZE + 2].copy_from_slice(&[TLV_NDEF, 0x10]);
+        assert_eq!(tag.ndef(), None);
+
+        // Three byte length format cut by the end of the data area.
+        tag.memory[HEADER_SIZE..].copy_from_slice(&[0, 0, 0, 0, 0, 0, TLV_NDEF, 0xff]);
+        assert_eq!(tag.ndef(), None);
+    }
+}
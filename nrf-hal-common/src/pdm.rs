@@ -0,0 +1,458 @@
+//! HAL interface for the PDM peripheral.
+//!
+//! The PDM (Pulse Density Modulation) peripheral captures audio from digital microphones. It
+//! generates the PDM clock, decimates the PDM stream of one (mono) or two (stereo) microphones
+//! sharing the data line, and writes 16 bit PCM samples to RAM through EasyDMA.
+//!
+//! Samples are captured continuously into two buffers: while one buffer is filled, the other one
+//! is handed over to the application (see [`Pdm::capture`]).
+
+#[cfg(not(feature = "5340-app"))]
+use crate::pac::{pdm, PDM as PDM_PAC};
+#[cfg(feature = "5340-app")]
+use crate::pac::{pdm0_ns as pdm, PDM0_NS as PDM_PAC};
+use crate::{
+    gpio::{Floating, Input, Output, Pin, PushPull},
+    target_constants::{SRAM_LOWER, SRAM_UPPER},
+};
+use core::sync::atomic::{compiler_fence, Ordering};
+use embedded_dma::*;
+
+use pdm::{EVENTS_END, EVENTS_STARTED, EVENTS_STOPPED, TASKS_START, TASKS_STOP};
+
+/// Interface to the PDM peripheral.
+pub struct Pdm {
+    pdm: PDM_PAC,
+}
+
+// PDM EasyDMA MAXCNT bit length = 15
+const MAX_DMA_MAXCNT: usize = (1 << 15) - 1;
+
+impl Pdm {
+    /// Takes ownership of the raw PDM peripheral, returning a safe wrapper.
+    ///
+    /// The peripheral is configured for a mono microphone, with the default clock frequency,
+    /// a decimation ratio of 64 and a gain of 0 dB.
+    pub fn new(pdm: PDM_PAC, pins: Pins) -> Self {
+        pdm.psel.clk.write(|w| {
+            unsafe { w.bits(pins.clk.psel_bits()) };
+            w.connect().connected()
+        });
+        pdm.psel.din.write(|w| {
+            unsafe { w.bits(pins.din.psel_bits()) };
+            w.connect().connected()
+        });
+
+        let pdm = Self { pdm };
+        pdm.set_channels(Channels::Mono)
+            .set_edge(Edge::LeftFalling)
+            .set_frequency(Frequency::Default)
+            .set_ratio(Ratio::_64x)
+            .set_gain(0, 0);
+        pdm
+    }
+
+    /// Enables the PDM module.
+    #[inline(always)]
+    pub fn enable(&self) -> &Self {
+        self.pdm.enable.write(|w| w.enable().enabled());
+        self
+    }
+
+    /// Disables the PDM module.
+    #[inline(always)]
+    pub fn disable(&self) -> &Self {
+        self.pdm.enable.write(|w| w.enable().disabled());
+        self
+    }
+
+    /// Selects mono or stereo operation.
+    ///
+    /// In stereo, the samples of the left and right channels are interleaved in the buffers,
+    /// starting with the left channel.
+    #[inline(always)]
+    pub fn set_channels(&self, channels: Channels) -> &Self {
+        self.pdm
+            .mode
+            .modify(|_, w| w.operation().bit(channels.into()));
+        self
+    }
+
+    /// Selects the clock edge on which the left channel is sampled.
+    #[inline(always)]
+    pub fn set_edge(&self, edge: Edge) -> &Self {
+        self.pdm.mode.modify(|_, w| w.edge().bit(edge.into()));
+        self
+    }
+
+    /// Sets the PDM clock frequency.
+    #[inline(always)]
+    pub fn set_frequency(&self, frequency: Frequency) -> &Self {
+        self.pdm
+            .pdmclkctrl
+            .write(|w| unsafe { w.bits(frequency.into()) });
+        self
+    }
+
+    /// Sets the decimation ratio between the PDM clock and the sample rate.
+    ///
+    /// The sample rate is the PDM clock frequency divided by the ratio, e.g. 16 kHz with the
+    /// default frequency and a ratio of 64.
+    #[inline(always)]
+    pub fn set_ratio(&self, ratio: Ratio) -> &Self {
+        self.pdm.ratio.write(|w| w.ratio().bit(ratio.into()));
+        self
+    }
+
+    /// Sets the gain of the left and right channels, in steps of 0.5 dB.
+    ///
+    /// The gains are clamped to the supported range, from -40 (-20 dB) to 40 (+20 dB).
+    #[inline(always)]
+    pub fn set_gain(&self, left: i8, right: i8) -> &Self {
+        self.pdm.gainl.write(|w| unsafe { w.bits(gain_bits(left)) });
+        self.pdm
+            .gainr
+            .write(|w| unsafe { w.bits(gain_bits(right)) });
+        self
+    }
+
+    /// Starts capturing samples into `buffers`, alternating between them.
+    ///
+    /// Both buffers must have the same, non-zero length, no more than 32767 samples, and be located
+    /// in RAM.
+    /// Returns a value that represents the in-progress capture.
+    #[allow(unused_mut)]
+    pub fn capture<B>(self, mut buffers: [B; 2]) -> Result<Transfer<B>, Error>
+    where
+        B: WriteBuffer<Word = i16> + 'static,
+    {
+        let (ptr0, len0) = unsafe { buffers[0].write_buffer() };
+        let (ptr1, len1) = unsafe { buffers[1].write_buffer() };
+        if len0 != len1 {
+            return Err(Error::BuffersDontMatch);
+        }
+        if len0 == 0 {
+            return Err(Error::BufferZeroLength);
+        }
+        if len0 > MAX_DMA_MAXCNT {
+            return Err(Error::BufferTooLong);
+        }
+        for ptr in [ptr0 as usize, ptr1 as usize] {
+            let end = ptr + len0 * core::mem::size_of::<i16>();
+            if ptr < SRAM_LOWER || end >= SRAM_UPPER {
+                return Err(Error::DMABufferNotInDataMemory);
+            }
+        }
+
+        self.pdm
+            .sample
+            .ptr
+            .write(|w| unsafe { w.sampleptr().bits(ptr0 as u32) });
+        self.pdm
+            .sample
+            .maxcnt
+            .write(|w| unsafe { w.buffsize().bits(len0 as u16) });
+
+        self.reset_event(PdmEvent::Started);
+        self.reset_event(PdmEvent::End);
+        self.reset_event(PdmEvent::Stopped);
+        self.enable();
+        // Conservative compiler fence to prevent optimizations that do not
+        // take in to account actions by DMA. The fence has been placed here,
+        // before any DMA action has started.
+        compiler_fence(Ordering::SeqCst);
+        self.pdm.tasks_start.write(|w| unsafe { w.bits(1) });
+
+        // Once the first buffer is latched, queue the second one.
+        while !self.is_event_triggered(PdmEvent::Started) {}
+        self.reset_event(PdmEvent::Started);
+        self.pdm
+            .sample
+            .ptr
+            .write(|w| unsafe { w.sampleptr().bits(ptr1 as u32) });
+
+        Ok(Transfer {
+            inner: Some(Inner {
+                buffers,
+                pdm: self,
+                filling: 0,
+            }),
+        })
+    }
+
+    /// Stops the capture and waits until it has stopped.
+    #[inline(always)]
+    pub fn stop(&self) -> &Self {
+        compiler_fence(Ordering::SeqCst);
+        self.pdm.tasks_stop.write(|w| unsafe { w.bits(1) });
+        while !self.is_event_triggered(PdmEvent::Stopped) {}
+        self.reset_event(PdmEvent::Stopped);
+        self
+    }
+
+    /// Checks if an event has been triggered.
+    #[inline(always)]
+    pub fn is_event_triggered(&self, event: PdmEvent) -> bool {
+        match event {
+            PdmEvent::Started => self.pdm.events_started.read().bits() != 0,
+            PdmEvent::Stopped => self.pdm.events_stopped.read().bits() != 0,
+            PdmEvent::End => self.pdm.events_end.read().bits() != 0,
+        }
+    }
+
+    /// Marks event as handled.
+    #[inline(always)]
+    pub fn reset_event(&self, event: PdmEvent) {
+        match event {
+            PdmEvent::Started => self.pdm.events_started.reset(),
+            PdmEvent::Stopped => self.pdm.events_stopped.reset(),
+            PdmEvent::End => self.pdm.events_end.reset(),
+        }
+    }
+
+    /// Enables interrupt triggering on the specified event.
+    #[inline(always)]
+    pub fn enable_interrupt(&self, event: PdmEvent) -> &Self {
+        match event {
+            PdmEvent::Started => self.pdm.intenset.write(|w| w.started().set()),
+            PdmEvent::Stopped => self.pdm.intenset.write(|w| w.stopped().set()),
+            PdmEvent::End => self.pdm.intenset.write(|w| w.end().set()),
+        };
+        self
+    }
+
+    /// Disables interrupt triggering on the specified event.
+    #[inline(always)]
+    pub fn disable_interrupt(&self, event: PdmEvent) -> &Self {
+        match event {
+            PdmEvent::Started => self.pdm.intenclr.write(|w| w.started().clear()),
+            PdmEvent::Stopped => self.pdm.intenclr.write(|w| w.stopped().clear()),
+            PdmEvent::End => self.pdm.intenclr.write(|w| w.end().clear()),
+        };
+        self
+    }
+
+    /// Returns reference to `Started` event endpoint for PPI.
+    #[inline(always)]
+    pub fn event_started(&self) -> &EVENTS_STARTED {
+        &self.pdm.events_started
+    }
+
+    /// Returns reference to `Stopped` event endpoint for PPI.
+    #[inline(always)]
+    pub fn event_stopped(&self) -> &EVENTS_STOPPED {
+        &self.pdm.events_stopped
+    }
+
+    /// Returns reference to `End` event endpoint for PPI.
+    #[inline(always)]
+    pub fn event_end(&self) -> &EVENTS_END {
+        &self.pdm.events_end
+    }
+
+    /// Returns reference to `Start` task endpoint for PPI.
+    #[inline(always)]
+    pub fn task_start(&self) -> &TASKS_START {
+        &self.pdm.tasks_start
+    }
+
+    /// Returns reference to `Stop` task endpoint for PPI.
+    #[inline(always)]
+    pub fn task_stop(&self) -> &TASKS_STOP {
+        &self.pdm.tasks_stop
+    }
+
+    /// Consumes `self` and returns back the raw peripheral.
+    pub fn free(self) -> (PDM_PAC, Pins) {
+        self.disable();
+        let clk = self.pdm.psel.clk.read().bits();
+        let din = self.pdm.psel.din.read().bits();
+        self.pdm.psel.clk.reset();
+        self.pdm.psel.din.reset();
+        (
+            self.pdm,
+            Pins {
+                clk: unsafe { Pin::from_psel_bits(clk) },
+                din: unsafe { Pin::from_psel_bits(din) },
+            },
+        )
+    }
+}
+
+fn gain_bits(gain: i8) -> u32 {
+    // 0x28 is 0 dB.
+    (0x28 + i32::from(gain.clamp(-40, 40))) as u32
+}
+
+/// Pins for the PDM peripheral.
+pub struct Pins {
+    /// PDM clock pin.
+    pub clk: Pin<Output<PushPull>>,
+    /// PDM data pin.
+    pub din: Pin<Input<Floating>>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    DMABufferNotInDataMemory,
+    BufferZeroLength,
+    BufferTooLong,
+    BuffersDontMatch,
+}
+
+/// Mono or stereo operation.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Channels {
+    Stereo,
+    Mono,
+}
+impl From<Channels> for bool {
+    fn from(variant: Channels) -> Self {
+        match variant {
+            Channels::Stereo => false,
+            Channels::Mono => true,
+        }
+    }
+}
+
+/// Clock edge on which the left channel is sampled.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Edge {
+    LeftFalling,
+    LeftRising,
+}
+impl From<Edge> for bool {
+    fn from(variant: Edge) -> Self {
+        match variant {
+            Edge::LeftFalling => false,
+            Edge::LeftRising => true,
+        }
+    }
+}
+
+/// PDM clock frequency.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Frequency {
+    /// 1.000 MHz.
+    _1000K = 0x08000000,
+    /// 1.032 MHz, the reset default.
+    Default = 0x08400000,
+    /// 1.067 MHz.
+    _1067K = 0x08800000,
+    /// 1.231 MHz.
+    _1231K = 0x09800000,
+    /// 1.280 MHz.
+    _1280K = 0x0A000000,
+    /// 1.333 MHz.
+    _1333K = 0x0A800000,
+}
+impl From<Frequency> for u32 {
+    fn from(variant: Frequency) -> Self {
+        variant as _
+    }
+}
+
+/// Ratio between the PDM clock and the sample rate.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Ratio {
+    _64x,
+    _80x,
+}
+impl From<Ratio> for bool {
+    fn from(variant: Ratio) -> Self {
+        match variant {
+            Ratio::_64x => false,
+            Ratio::_80x => true,
+        }
+    }
+}
+
+/// PDM events
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum PdmEvent {
+    Started,
+    Stopped,
+    End,
+}
+
+/// A double-buffered DMA capture, started by [`Pdm::capture`].
+pub struct Transfer<B> {
+    // FIXME: Always `Some`, only using `Option` here to allow moving fields out of `inner`.
+    inner: Option<Inner<B>>,
+}
+
+struct Inner<B> {
+    buffers: [B; 2],
+    pdm: Pdm,
+    /// Index of the buffer being filled.
+    filling: usize,
+}
+
+impl<B> Transfer<B>
+where
+    B: WriteBuffer<Word = i16> + 'static,
+{
+    /// Returns `true` if a buffer is full, so [`next`](Self::next) will not block.
+    pub fn is_ready(&self) -> bool {
+        self.inner().pdm.is_event_triggered(PdmEvent::End)
+    }
+
+    /// Waits for the buffer being filled to be full and passes its samples to `f`.
+    ///
+    /// The other buffer is filled meanwhile. `f` must return before it is full, otherwise the
+    /// samples are lost.
+    pub fn next<R>(&mut self, f: impl FnOnce(&[i16]) -> R) -> R {
+        let inner = self
+            .inner
+            .as_mut()
+            .unwrap_or_else(|| unsafe { core::hint::unreachable_unchecked() });
+        let pdm = &inner.pdm;
+
+        while !pdm.is_event_triggered(PdmEvent::End) {}
+        pdm.reset_event(PdmEvent::End);
+        // STARTED signals that the other buffer has been latched, the full one is no longer
+        // written by EasyDMA.
+        while !pdm.is_event_triggered(PdmEvent::Started) {}
+        pdm.reset_event(PdmEvent::Started);
+        compiler_fence(Ordering::Acquire);
+
+        let (ptr, len) = unsafe { inner.buffers[inner.filling].write_buffer() };
+        let result = f(unsafe { core::slice::from_raw_parts(ptr, len) });
+        compiler_fence(Ordering::SeqCst);
+
+        // Use the buffer again for the capture after the current one.
+        pdm.pdm
+            .sample
+            .ptr
+            .write(|w| unsafe { w.sampleptr().bits(ptr as u32) });
+        inner.filling ^= 1;
+
+        result
+    }
+
+    /// Stops the capture and returns the buffers and the PDM peripheral.
+    pub fn stop(mut self) -> ([B; 2], Pdm) {
+        let inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { core::hint::unreachable_unchecked() });
+        inner.pdm.stop().disable();
+        compiler_fence(Ordering::Acquire);
+        (inner.buffers, inner.pdm)
+    }
+
+    fn inner(&self) -> &Inner<B> {
+        self.inner
+            .as_ref()
+            .unwrap_or_else(|| unsafe { core::hint::unreachable_unchecked() })
+    }
+}
+
+impl<B> Drop for Transfer<B> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.pdm.stop().disable();
+            compiler_fence(Ordering::Acquire);
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! HAL interface to the POWER peripheral.
+//!
+//! The POWER peripheral controls the power supply of the chip: it allows entering System OFF,
+//! reading the reason for the last reset, selecting the System ON sub power mode and enabling the
+//! DC/DC regulators. It also provides the general purpose retention registers (`GPREGRET`), which
+//! keep their value across all resets except power-on, brownout and pin resets.
+
+use crate::gpio::{Input, Level, Pin, Sense};
+use crate::pac::POWER;
+
+/// Interface to the POWER peripheral.
+pub struct Power(POWER);
+
+impl Power {
+    /// Takes ownership of the `POWER` peripheral.
+    pub fn new(power: POWER) -> Self {
+        Self(power)
+    }
+
+    /// Releases the `POWER` peripheral.
+    pub fn free(self) -> POWER {
+        self.0
+    }
+
+    /// Returns the reasons for the last reset(s).
+    ///
+    /// The `RESETREAS` register is cumulative: unless it is cleared with
+    /// [`clear_reset_reasons`](Self::clear_reset_reasons), it accumulates the reasons of all
+    /// resets since the last power-on or brownout reset.
+    pub fn reset_reasons(&self) -> ResetReasons {
+        ResetReasons(self.0.resetreas.read().bits() & ResetReasons::all().0)
+    }
+
+    /// Clears the given reasons from the `RESETREAS` register.
+    pub fn clear_reset_reasons(&mut self, reasons: ResetReasons) {
+        self.0.resetreas.write(|w| unsafe { w.bits(reasons.0) });
+    }
+
+    /// Reads the general purpose retention register `GPREGRET`.
+    pub fn gpregret(&self) -> u8 {
+        self.0.gpregret.read().bits() as u8
+    }
+
+    /// Writes the general purpose retention register `GPREGRET`.
+    pub fn set_gpregret(&mut self, value: u8) {
+        self.0.gpregret.write(|w| unsafe { w.bits(value as u32) });
+    }
+
+    /// Reads the general purpose retention register `GPREGRET2`.
+    #[cfg(not(feature = "51"))]
+    pub fn gpregret2(&self) -> u8 {
+        self.0.gpregret2.read().bits() as u8
+    }
+
+    /// Writes the general purpose retention register `GPREGRET2`.
+    #[cfg(not(feature = "51"))]
+    pub fn set_gpregret2(&mut self, value: u8) {
+        self.0.gpregret2.write(|w| unsafe { w.bits(value as u32) });
+    }
+
+    /// Selects the sub power mode used while in System ON.
+    pub fn set_sub_power_mode(&mut self, mode: SubPowerMode) {
+        match mode {
+            SubPowerMode::ConstantLatency => {
+                self.0.tasks_constlat.write(|w| unsafe { w.bits(1) });
+            }
+            SubPowerMode::LowPower => {
+                self.0.tasks_lowpwr.write(|w| unsafe { w.bits(1) });
+            }
+        }
+    }
+
+    /// Enables or disables the DC/DC converter (`DCDCEN`).
+    ///
+    /// On the nRF52833 and nRF52840 this controls the REG1 stage; see
+    /// [`set_dcdc0`](Self::set_dcdc0) for the REG0 stage.
+    ///
+    /// The DC/DC converter requires the external inductors to be present, the chip will not
+    /// operate correctly if it is enabled without them.
+    pub fn set_dcdc(&mut self, enabled: bool) {
+        self.0.dcdcen.write(|w| w.dcdcen().bit(enabled));
+    }
+
+    /// Enables or disables the DC/DC converter of the REG0 stage (`DCDCEN0`).
+    ///
+    /// REG0 is only used when the chip is supplied through VDDH.
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    pub fn set_dcdc0(&mut self, enabled: bool) {
+        self.0.dcdcen0.write(|w| w.dcdcen().bit(enabled));
+    }
+
+    /// Enters System OFF.
+    ///
+    /// The chip can only be woken up by the sources configured in `wake`, or by a reset. Waking
+    /// up from System OFF always resets the chip, so this function never returns.
+    ///
+    /// When a debugger is attached, System OFF is emulated and the CPU keeps running; this
+    /// function then spins until the chip is reset.
+    pub fn system_off(self, wake: &WakeSources) -> ! {
+        #[cfg(any(feature = "52832", feature = "52833", feature = "52840"))]
+        if wake.nfc {
+            // The NFCT peripheral has to be in the SENSE state to detect a field in System OFF.
+            let nfct = unsafe { &*crate::pac::NFCT::ptr() };
+            nfct.tasks_sense.write(|w| unsafe { w.bits(1) });
+        }
+        #[cfg(not(any(feature = "52832", feature = "52833", feature = "52840")))]
+        let _ = wake;
+
+        self.0.systemoff.write(|w| w.systemoff().enter());
+        cortex_m::asm::dsb();
+
+        loop {
+            cortex_m::asm::wfe();
+        }
+    }
+}
+
+/// Sub power modes available in System ON.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SubPowerMode {
+    /// Keeps resources running to ensure the lowest possible wake-up latency, at the cost of a
+    /// higher current consumption.
+    ConstantLatency,
+    /// Automatically turns off unused resources (the reset default).
+    LowPower,
+}
+
+/// A single reason for a reset, as reported in `RESETREAS`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u32)]
+pub enum ResetReason {
+    /// Reset from the pin reset.
+    ResetPin = 1 << 0,
+    /// Reset from the watchdog.
+    Watchdog = 1 << 1,
+    /// Soft reset, e.g. from `SCB::sys_reset`.
+    SoftReset = 1 << 2,
+    /// Reset from CPU lock-up.
+    Lockup = 1 << 3,
+    /// Wake-up from System OFF by the DETECT signal from GPIO.
+    Off = 1 << 16,
+    /// Wake-up from System OFF by the ANADETECT signal from LPCOMP.
+    #[cfg(not(any(feature = "52805", feature = "52810", feature = "52811")))]
+    Lpcomp = 1 << 17,
+    /// Wake-up from System OFF by entering debug interface mode.
+    DebugInterface = 1 << 18,
+    /// Wake-up from System OFF by an NFC field.
+    #[cfg(any(feature = "52832", feature = "52833", feature = "52840"))]
+    Nfc = 1 << 19,
+    /// Wake-up from System OFF by VBUS rising into the valid range.
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    Vbus = 1 << 20,
+}
+
+impl ResetReason {
+    const ALL: &'static [ResetReason] = &[
+        ResetReason::ResetPin,
+        ResetReason::Watchdog,
+        ResetReason::SoftReset,
+        ResetReason::Lockup,
+        ResetReason::Off,
+        #[cfg(not(any(feature = "52805", feature = "52810", feature = "52811")))]
+        ResetReason::Lpcomp,
+        ResetReason::DebugInterface,
+        #[cfg(any(feature = "52832", feature = "52833", feature = "52840"))]
+        ResetReason::Nfc,
+        #[cfg(any(feature = "52833", feature = "52840"))]
+        ResetReason::Vbus,
+    ];
+}
+
+/// A set of [`ResetReason`]s.
+///
+/// If the set is empty, the last reset was a power-on or brownout reset.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ResetReasons(u32);
+
+impl ResetReasons {
+    /// Returns an empty set.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns the set of all reset reasons supported by this chip.
+    pub fn all() -> Self {
+        ResetReason::ALL
+            .iter()
+            .fold(Self::empty(), |set, reason| set.with(*reason))
+    }
+
+    /// Returns `true` if the set contains no reason.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if `reason` is part of the set.
+    pub fn contains(&self, reason: ResetReason) -> bool {
+        self.0 & reason as u32 != 0
+    }
+
+    /// Returns the set with `reason` added.
+    pub fn with(self, reason: ResetReason) -> Self {
+        Self(self.0 | reason as u32)
+    }
+
+    /// Returns the raw `RESETREAS` bits of this set.
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns an iterator over the reasons in the set.
+    pub fn iter(&self) -> impl Iterator<Item = ResetReason> + '_ {
+        ResetReason::ALL
+            .iter()
+            .copied()
+            .filter(move |reason| self.contains(*reason))
+    }
+}
+
+impl From<ResetReason> for ResetReasons {
+    fn from(reason: ResetReason) -> Self {
+        Self(reason as u32)
+    }
+}
+
+/// Sources that can wake the chip up from System OFF.
+///
+/// Apart from the sources configured here, the chip can be woken up by the low power comparator
+/// if it was enabled with its analog detect event configured (see the `lpcomp` module), and always
+/// by a reset.
+#[derive(Default)]
+pub struct WakeSources {
+    #[cfg(any(feature = "52832", feature = "52833", feature = "52840"))]
+    nfc: bool,
+}
+
+impl WakeSources {
+    /// Creates an empty set of wake-up sources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wakes the chip up when `pin` reaches `level`.
+    ///
+    /// This configures the SENSE mechanism of the pin right away. Note that the chip wakes up
+    /// immediately if the pin is already at `level` when entering System OFF.
+    pub fn pin<MODE>(self, pin: &mut Pin<Input<MODE>>, level: Level) -> Self {
+        pin.set_sense(match level {
+            Level::Low => Sense::Low,
+            Level::High => Sense::High,
+        });
+        self
+    }
+
+    /// Wakes the chip up when an NFC field is detected.
+    #[cfg(any(feature = "52832", feature = "52833", feature = "52840"))]
+    pub fn nfc_field(mut self) -> Self {
+        self.nfc = true;
+        self
+    }
+}
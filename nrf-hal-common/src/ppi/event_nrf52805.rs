@@ -100,3 +100,5 @@ impl Event for crate::pac::saadc::EVENTS_DONE {}
 impl Event for crate::pac::saadc::EVENTS_RESULTDONE {}
 impl Event for crate::pac::saadc::EVENTS_CALIBRATEDONE {}
 impl Event for crate::pac::saadc::EVENTS_STOPPED {}
+impl Event for crate::pac::saadc::events_ch::LIMITH {}
+impl Event for crate::pac::saadc::events_ch::LIMITL {}
@@ -103,6 +103,8 @@ impl Event for crate::pac::saadc::EVENTS_DONE {}
 impl Event for crate::pac::saadc::EVENTS_RESULTDONE {}
 impl Event for crate::pac::saadc::EVENTS_CALIBRATEDONE {}
 impl Event for crate::pac::saadc::EVENTS_STOPPED {}
+impl Event for crate::pac::saadc::events_ch::LIMITH {}
+impl Event for crate::pac::saadc::events_ch::LIMITL {}
 impl Event for crate::pac::comp::EVENTS_READY {}
 impl Event for crate::pac::comp::EVENTS_DOWN {}
 impl Event for crate::pac::comp::EVENTS_UP {}
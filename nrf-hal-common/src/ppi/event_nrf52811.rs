@@ -108,6 +108,8 @@ impl Event for crate::pac::saadc::EVENTS_DONE {}
 impl Event for crate::pac::saadc::EVENTS_RESULTDONE {}
 impl Event for crate::pac::saadc::EVENTS_CALIBRATEDONE {}
 impl Event for crate::pac::saadc::EVENTS_STOPPED {}
+impl Event for crate::pac::saadc::events_ch::LIMITH {}
+impl Event for crate::pac::saadc::events_ch::LIMITL {}
 impl Event for crate::pac::spim0::EVENTS_STOPPED {}
 impl Event for crate::pac::spim0::EVENTS_ENDRX {}
 impl Event for crate::pac::spim0::EVENTS_END {}
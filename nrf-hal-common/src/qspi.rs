@@ -0,0 +1,646 @@
+//! HAL interface to the QSPI peripheral.
+//!
+//! The QSPI peripheral interfaces with external flash memories over one, two or four data lines.
+//! Besides EasyDMA reads, writes and erases, it supports custom instructions to access the
+//! registers of the flash device, and maps the flash in the address space for execute in place
+//! (XIP) reads.
+//!
+//! [`Qspi`] implements the `embedded-storage` `NorFlash` traits, so the same storage code can run
+//! on the internal flash ([`Nvmc`](crate::nvmc::Nvmc)) and on the external flash.
+
+use core::sync::atomic::{compiler_fence, Ordering::SeqCst};
+
+use embedded_storage::nor_flash::{
+    ErrorType, NorFlash, NorFlashError, NorFlashErrorKind, ReadNorFlash,
+};
+
+#[cfg(feature = "52840")]
+use crate::pac::QSPI;
+#[cfg(feature = "5340-app")]
+use crate::pac::QSPI_NS as QSPI;
+
+use crate::gpio::{Output, Pin, PushPull};
+use crate::slice_in_ram;
+
+/// Start of the XIP region in the address space.
+#[cfg(feature = "52840")]
+const XIP_START: usize = 0x1200_0000;
+#[cfg(feature = "5340-app")]
+const XIP_START: usize = 0x1000_0000;
+
+/// Maximum size of the XIP region.
+const XIP_SIZE: usize = 0x0800_0000;
+
+/// Largest number of bytes moved by a single EasyDMA transfer, kept word aligned.
+const MAX_TRANSFER: usize = 0x3fffc;
+
+const WORD_SIZE: usize = core::mem::size_of::<u32>();
+const SECTOR_SIZE: usize = 4 * 1024;
+const BLOCK_SIZE: usize = 64 * 1024;
+
+/// Size of the buffer used to bounce transfers whose RAM buffer is not suitable for EasyDMA.
+const BOUNCE_SIZE: usize = 64;
+
+/// Interface to the QSPI peripheral.
+pub struct Qspi {
+    qspi: QSPI,
+    capacity: usize,
+    xip_offset: usize,
+    deep_power_down: bool,
+}
+
+impl Qspi {
+    /// Takes ownership of the QSPI peripheral and activates it.
+    ///
+    /// The pins should be configured with their idle level high, e.g. with
+    /// `into_push_pull_output_drive(Level::High, DriveConfig::HighDrive0HighDrive1)`: high drive
+    /// strength is recommended for the higher clock frequencies.
+    ///
+    /// The flash device is left in its current state. If `config` selects a quad opcode or 32 bit
+    /// addressing, the matching mode of the device (e.g. its Quad Enable bit) has to be set with
+    /// [`custom_instruction`](Self::custom_instruction).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `config.frequency` is larger than 15, or if `config.xip_offset` is larger than
+    /// `config.capacity`.
+    pub fn new(qspi: QSPI, pins: Pins, config: &Config) -> Self {
+        qspi.psel
+            .sck
+            .write(|w| unsafe { w.bits(pins.sck.psel_bits()) });
+        qspi.psel
+            .csn
+            .write(|w| unsafe { w.bits(pins.csn.psel_bits()) });
+        qspi.psel
+            .io0
+            .write(|w| unsafe { w.bits(pins.io0.psel_bits()) });
+        qspi.psel
+            .io1
+            .write(|w| unsafe { w.bits(pins.io1.psel_bits()) });
+        match pins.io2 {
+            Some(io2) => qspi.psel.io2.write(|w| unsafe { w.bits(io2.psel_bits()) }),
+            None => qspi.psel.io2.write(|w| w.connect().disconnected()),
+        }
+        match pins.io3 {
+            Some(io3) => qspi.psel.io3.write(|w| unsafe { w.bits(io3.psel_bits()) }),
+            None => qspi.psel.io3.write(|w| w.connect().disconnected()),
+        }
+
+        assert!(config.frequency <= 15);
+        assert!(config.xip_offset <= config.capacity);
+        qspi.xipoffset
+            .write(|w| unsafe { w.bits(config.xip_offset as u32) });
+
+        let deep_power_down = config.deep_power_down.is_some();
+        if let Some(dpm) = &config.deep_power_down {
+            qspi.dpmdur.write(|w| unsafe {
+                w.enter().bits(dpm.enter_duration);
+                w.exit().bits(dpm.exit_duration);
+                w
+            });
+        }
+
+        qspi.ifconfig0.write(|w| {
+            match config.read_opcode {
+                ReadOpcode::FastRead => w.readoc().fastread(),
+                ReadOpcode::Read2O => w.readoc().read2o(),
+                ReadOpcode::Read2IO => w.readoc().read2io(),
+                ReadOpcode::Read4O => w.readoc().read4o(),
+                ReadOpcode::Read4IO => w.readoc().read4io(),
+            };
+            match config.write_opcode {
+                WriteOpcode::PageProgram => w.writeoc().pp(),
+                WriteOpcode::PP2O => w.writeoc().pp2o(),
+                WriteOpcode::PP4O => w.writeoc().pp4o(),
+                WriteOpcode::PP4IO => w.writeoc().pp4io(),
+            };
+            match config.address_mode {
+                AddressMode::_24Bit => w.addrmode()._24bit(),
+                AddressMode::_32Bit => w.addrmode()._32bit(),
+            };
+            w.dpmenable().bit(deep_power_down);
+            match config.write_page_size {
+                WritePageSize::_256Bytes => w.ppsize()._256bytes(),
+                WritePageSize::_512Bytes => w.ppsize()._512bytes(),
+            };
+            w
+        });
+        qspi.ifconfig1.write(|w| unsafe {
+            w.sckdelay().bits(config.sck_delay);
+            w.dpmen().exit();
+            match config.spi_mode {
+                SpiMode::Mode0 => w.spimode().mode0(),
+                SpiMode::Mode3 => w.spimode().mode3(),
+            };
+            w.sckfreq().bits(config.frequency);
+            w
+        });
+
+        qspi.enable.write(|w| w.enable().enabled());
+
+        let qspi = Qspi {
+            qspi,
+            capacity: config.capacity,
+            xip_offset: config.xip_offset,
+            deep_power_down,
+        };
+        qspi.qspi.events_ready.reset();
+        qspi.qspi.tasks_activate.write(|w| unsafe { w.bits(1) });
+        qspi.wait_ready();
+        qspi
+    }
+
+    /// Returns the capacity of the flash device, in bytes.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Reads `buf.len()` bytes from the flash, starting at `address`.
+    ///
+    /// `address` and the length of `buf` must be multiples of 4. Buffers that EasyDMA can not
+    /// access directly (not in RAM or not word aligned) are read through an intermediate buffer.
+    pub fn read(&mut self, address: u32, buf: &mut [u8]) -> Result<(), Error> {
+        self.check_range(address as usize, buf.len(), WORD_SIZE)?;
+
+        if is_dma_compatible(buf) {
+            for (offset, chunk) in (0..)
+                .step_by(MAX_TRANSFER)
+                .zip(buf.chunks_mut(MAX_TRANSFER))
+            {
+                self.read_dma(address as usize + offset, chunk);
+            }
+        } else {
+            let mut bounce = Bounce([0; BOUNCE_SIZE]);
+            for (offset, chunk) in (0..).step_by(BOUNCE_SIZE).zip(buf.chunks_mut(BOUNCE_SIZE)) {
+                let bounce = &mut bounce.0[..chunk.len()];
+                self.read_dma(address as usize + offset, bounce);
+                chunk.copy_from_slice(bounce);
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes `data` to the flash, starting at `address`.
+    ///
+    /// `address` and the length of `data` must be multiples of 4. Writes are split in page program
+    /// operations by the peripheral, according to [`Config::write_page_size`]. The written area
+    /// must have been erased before.
+    pub fn write(&mut self, address: u32, data: &[u8]) -> Result<(), Error> {
+        self.check_range(address as usize, data.len(), WORD_SIZE)?;
+
+        if is_dma_compatible(data) {
+            for (offset, chunk) in (0..).step_by(MAX_TRANSFER).zip(data.chunks(MAX_TRANSFER)) {
+                self.write_dma(address as usize + offset, chunk);
+            }
+        } else {
+            let mut bounce = Bounce([0; BOUNCE_SIZE]);
+            for (offset, chunk) in (0..).step_by(BOUNCE_SIZE).zip(data.chunks(BOUNCE_SIZE)) {
+                let bounce = &mut bounce.0[..chunk.len()];
+                bounce.copy_from_slice(chunk);
+                self.write_dma(address as usize + offset, bounce);
+            }
+        }
+        Ok(())
+    }
+
+    /// Erases the 4 KiB sector starting at `address`.
+    pub fn erase_sector(&mut self, address: u32) -> Result<(), Error> {
+        self.check_range(address as usize, SECTOR_SIZE, SECTOR_SIZE)?;
+        self.erase_unit(address, EraseLength::Sector);
+        Ok(())
+    }
+
+    /// Erases the 64 KiB block starting at `address`.
+    pub fn erase_block(&mut self, address: u32) -> Result<(), Error> {
+        self.check_range(address as usize, BLOCK_SIZE, BLOCK_SIZE)?;
+        self.erase_unit(address, EraseLength::Block);
+        Ok(())
+    }
+
+    /// Erases the whole flash device.
+    pub fn erase_chip(&mut self) {
+        self.erase_unit(0, EraseLength::All);
+    }
+
+    /// Sends a custom instruction (`CINSTR`) to the flash device.
+    ///
+    /// The bytes of `tx` are sent after `opcode`, and the bytes received meanwhile are stored in
+    /// `rx`. The data phase lasts for the longest of both buffers, which must not exceed 8 bytes.
+    ///
+    /// IO2 and IO3 are held high during the transfer, so they can be used as the WP and HOLD
+    /// signals of the device.
+    pub fn custom_instruction(
+        &mut self,
+        opcode: u8,
+        tx: &[u8],
+        rx: &mut [u8],
+    ) -> Result<(), Error> {
+        let len = tx.len().max(rx.len());
+        if len > 8 {
+            return Err(Error::InvalidLength);
+        }
+
+        let mut data = [0; 8];
+        data[..tx.len()].copy_from_slice(tx);
+        self.qspi
+            .cinstrdat0
+            .write(|w| unsafe { w.bits(u32::from_le_bytes([data[0], data[1], data[2], data[3]])) });
+        self.qspi
+            .cinstrdat1
+            .write(|w| unsafe { w.bits(u32::from_le_bytes([data[4], data[5], data[6], data[7]])) });
+
+        self.wait_idle();
+        self.qspi.events_ready.reset();
+        // LENGTH counts the opcode, and IO2/IO3 are kept high.
+        self.qspi.cinstrconf.write(|w| unsafe {
+            w.opcode().bits(opcode);
+            w.length().bits(len as u8 + 1);
+            w.lio2().set_bit();
+            w.lio3().set_bit();
+            w
+        });
+        self.wait_ready();
+
+        data[..4].copy_from_slice(&self.qspi.cinstrdat0.read().bits().to_le_bytes());
+        data[4..].copy_from_slice(&self.qspi.cinstrdat1.read().bits().to_le_bytes());
+        rx.copy_from_slice(&data[..rx.len()]);
+        Ok(())
+    }
+
+    /// Returns the last value of the status register of the flash device, as read by the
+    /// peripheral.
+    pub fn status_register(&self) -> u8 {
+        self.qspi.status.read().sreg().bits()
+    }
+
+    /// Returns the flash contents as mapped in the XIP region, starting at [`Config::xip_offset`].
+    ///
+    /// XIP reads use the read opcode of the configuration. The slice can not be held across
+    /// writes, erases or deep power-down, which all take `&mut self`.
+    pub fn xip(&self) -> &[u8] {
+        let len = (self.capacity - self.xip_offset).min(XIP_SIZE);
+        unsafe { core::slice::from_raw_parts(XIP_START as *const u8, len) }
+    }
+
+    /// Puts the flash device in deep power-down mode.
+    ///
+    /// The device is woken up again with [`exit_deep_power_down`](Self::exit_deep_power_down).
+    /// Returns an error if deep power-down was not enabled in the [`Config`].
+    pub fn enter_deep_power_down(&mut self) -> Result<(), Error> {
+        self.set_deep_power_down(true)
+    }
+
+    /// Wakes the flash device up from deep power-down mode.
+    pub fn exit_deep_power_down(&mut self) -> Result<(), Error> {
+        self.set_deep_power_down(false)
+    }
+
+    /// Returns `true` if the flash device is in deep power-down mode.
+    pub fn is_in_deep_power_down(&self) -> bool {
+        self.qspi.status.read().dpm().is_enabled()
+    }
+
+    /// Deactivates and disables the peripheral, and releases it with its pins.
+    pub fn free(self) -> (QSPI, Pins) {
+        self.wait_idle();
+        self.qspi.tasks_deactivate.write(|w| unsafe { w.bits(1) });
+        self.qspi.enable.write(|w| w.enable().disabled());
+
+        let io2 = self.qspi.psel.io2.read();
+        let io3 = self.qspi.psel.io3.read();
+        let pins = unsafe {
+            Pins {
+                sck: Pin::from_psel_bits(self.qspi.psel.sck.read().bits()),
+                csn: Pin::from_psel_bits(self.qspi.psel.csn.read().bits()),
+                io0: Pin::from_psel_bits(self.qspi.psel.io0.read().bits()),
+                io1: Pin::from_psel_bits(self.qspi.psel.io1.read().bits()),
+                io2: if io2.connect().is_connected() {
+                    Some(Pin::from_psel_bits(io2.bits()))
+                } else {
+                    None
+                },
+                io3: if io3.connect().is_connected() {
+                    Some(Pin::from_psel_bits(io3.bits()))
+                } else {
+                    None
+                },
+            }
+        };
+        self.qspi.psel.sck.reset();
+        self.qspi.psel.csn.reset();
+        self.qspi.psel.io0.reset();
+        self.qspi.psel.io1.reset();
+        self.qspi.psel.io2.reset();
+        self.qspi.psel.io3.reset();
+
+        (self.qspi, pins)
+    }
+
+    fn set_deep_power_down(&mut self, enter: bool) -> Result<(), Error> {
+        if !self.deep_power_down {
+            return Err(Error::DeepPowerDownDisabled);
+        }
+        self.wait_idle();
+        self.qspi.ifconfig1.modify(|_, w| w.dpmen().bit(enter));
+        while self.is_in_deep_power_down() != enter {}
+        Ok(())
+    }
+
+    fn check_range(&self, address: usize, len: usize, align: usize) -> Result<(), Error> {
+        if len > self.capacity || address > self.capacity - len {
+            return Err(Error::OutOfBounds);
+        }
+        if address % align != 0 || len % align != 0 {
+            return Err(Error::Unaligned);
+        }
+        Ok(())
+    }
+
+    fn read_dma(&mut self, address: usize, buf: &mut [u8]) {
+        self.wait_idle();
+        self.qspi
+            .read
+            .src
+            .write(|w| unsafe { w.bits(address as u32) });
+        self.qspi
+            .read
+            .dst
+            .write(|w| unsafe { w.bits(buf.as_mut_ptr() as u32) });
+        self.qspi
+            .read
+            .cnt
+            .write(|w| unsafe { w.bits(buf.len() as u32) });
+
+        // Conservative compiler fence to prevent optimizations that do not
+        // take in to account actions by DMA. The fence has been placed here,
+        // before any DMA action has started.
+        compiler_fence(SeqCst);
+        self.qspi.events_ready.reset();
+        self.qspi.tasks_readstart.write(|w| unsafe { w.bits(1) });
+        self.wait_ready();
+        // Conservative compiler fence to prevent optimizations that do not
+        // take in to account actions by DMA. The fence has been placed here,
+        // after all possible DMA actions have completed.
+        compiler_fence(SeqCst);
+    }
+
+    fn write_dma(&mut self, address: usize, data: &[u8]) {
+        self.wait_idle();
+        self.qspi
+            .write
+            .dst
+            .write(|w| unsafe { w.bits(address as u32) });
+        self.qspi
+            .write
+            .src
+            .write(|w| unsafe { w.bits(data.as_ptr() as u32) });
+        self.qspi
+            .write
+            .cnt
+            .write(|w| unsafe { w.bits(data.len() as u32) });
+
+        compiler_fence(SeqCst);
+        self.qspi.events_ready.reset();
+        self.qspi.tasks_writestart.write(|w| unsafe { w.bits(1) });
+        self.wait_ready();
+        compiler_fence(SeqCst);
+    }
+
+    fn erase_unit(&mut self, address: u32, len: EraseLength) {
+        self.wait_idle();
+        self.qspi.erase.ptr.write(|w| unsafe { w.bits(address) });
+        self.qspi.erase.len.write(|w| unsafe { w.bits(len as u32) });
+        self.qspi.events_ready.reset();
+        self.qspi.tasks_erasestart.write(|w| unsafe { w.bits(1) });
+        self.wait_ready();
+    }
+
+    /// Waits for the READY event, signalling the end of the current operation.
+    #[inline]
+    fn wait_ready(&self) {
+        while self.qspi.events_ready.read().bits() == 0 {}
+        self.qspi.events_ready.reset();
+    }
+
+    /// Waits for the peripheral to be ready for a new operation, including for the end of the
+    /// write or erase in progress in the flash device.
+    #[inline]
+    fn wait_idle(&self) {
+        while self.qspi.status.read().ready().is_busy() {}
+    }
+}
+
+impl ErrorType for Qspi {
+    type Error = Error;
+}
+
+impl ReadNorFlash for Qspi {
+    const READ_SIZE: usize = WORD_SIZE;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+        Qspi::read(self, offset, bytes)
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl NorFlash for Qspi {
+    const WRITE_SIZE: usize = WORD_SIZE;
+
+    const ERASE_SIZE: usize = SECTOR_SIZE;
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+        let (from, to) = (from as usize, to as usize);
+        if from > to {
+            return Err(Error::OutOfBounds);
+        }
+        self.check_range(from, to - from, SECTOR_SIZE)?;
+
+        // Use block erases where possible, they are much faster than erasing all their sectors.
+        let mut address = from;
+        while address < to {
+            if address % BLOCK_SIZE == 0 && to - address >= BLOCK_SIZE {
+                self.erase_unit(address as u32, EraseLength::Block);
+                address += BLOCK_SIZE;
+            } else {
+                self.erase_unit(address as u32, EraseLength::Sector);
+                address += SECTOR_SIZE;
+            }
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+        Qspi::write(self, offset, bytes)
+    }
+}
+
+/// Checks if EasyDMA can access `buf` directly.
+fn is_dma_compatible(buf: &[u8]) -> bool {
+    buf.as_ptr() as usize % WORD_SIZE == 0 && slice_in_ram(buf)
+}
+
+#[repr(align(4))]
+struct Bounce([u8; BOUNCE_SIZE]);
+
+/// `ERASE.LEN` values.
+#[derive(Clone, Copy)]
+enum EraseLength {
+    Sector = 0,
+    Block = 1,
+    All = 2,
+}
+
+/// GPIO pins for the QSPI interface.
+pub struct Pins {
+    /// Serial clock.
+    pub sck: Pin<Output<PushPull>>,
+    /// Chip select.
+    pub csn: Pin<Output<PushPull>>,
+    /// IO0, the data output (MOSI) in single line mode.
+    pub io0: Pin<Output<PushPull>>,
+    /// IO1, the data input (MISO) in single line mode.
+    pub io1: Pin<Output<PushPull>>,
+    /// IO2, only needed for quad line opcodes.
+    pub io2: Option<Pin<Output<PushPull>>>,
+    /// IO3, only needed for quad line opcodes.
+    pub io3: Option<Pin<Output<PushPull>>>,
+}
+
+/// Configuration of the QSPI peripheral and of the flash device it drives.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Opcode used for reads, and for XIP.
+    pub read_opcode: ReadOpcode,
+    /// Opcode used for writes.
+    pub write_opcode: WriteOpcode,
+    /// Number of address bytes.
+    pub address_mode: AddressMode,
+    /// Page size of the flash device, writes are split at page boundaries.
+    pub write_page_size: WritePageSize,
+    /// Clock divider: the serial clock runs at the QSPI base clock divided by `frequency + 1`.
+    ///
+    /// The base clock is 32 MHz on the nRF52840, see the product specification for the nRF5340.
+    /// Must be at most 15.
+    pub frequency: u8,
+    /// Minimum time between the falling edge of CSN and the first clock edge, in units of
+    /// 62.5 ns.
+    pub sck_delay: u8,
+    /// Clock polarity and phase.
+    pub spi_mode: SpiMode,
+    /// Deep power-down timings, `None` if deep power-down is not used.
+    pub deep_power_down: Option<DeepPowerDownConfig>,
+    /// Size of the flash device, in bytes. Must be set, the default is 0.
+    pub capacity: usize,
+    /// Address of the flash device mapped at the start of the XIP region.
+    pub xip_offset: usize,
+}
+
+impl Default for Config {
+    /// Single line reads and writes with 24 bit addresses, at 8 MHz on the nRF52840.
+    fn default() -> Self {
+        Self {
+            read_opcode: ReadOpcode::FastRead,
+            write_opcode: WriteOpcode::PageProgram,
+            address_mode: AddressMode::_24Bit,
+            write_page_size: WritePageSize::_256Bytes,
+            frequency: 3,
+            sck_delay: 80,
+            spi_mode: SpiMode::Mode0,
+            deep_power_down: None,
+            capacity: 0,
+            xip_offset: 0,
+        }
+    }
+}
+
+/// Timings of the deep power-down mode of the flash device.
+#[derive(Clone, Copy, Debug)]
+pub struct DeepPowerDownConfig {
+    /// Time needed by the device to enter deep power-down, in units of 16 µs.
+    pub enter_duration: u16,
+    /// Time needed by the device to exit deep power-down, in units of 16 µs.
+    pub exit_duration: u16,
+}
+
+/// Opcodes used for reads (`IFCONFIG0.READOC`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReadOpcode {
+    /// Single line read, opcode `0x0B`.
+    FastRead = 0,
+    /// Dual output read, opcode `0x3B`.
+    Read2O = 1,
+    /// Dual I/O read, opcode `0xBB`.
+    Read2IO = 2,
+    /// Quad output read, opcode `0x6B`.
+    Read4O = 3,
+    /// Quad I/O read, opcode `0xEB`.
+    Read4IO = 4,
+}
+
+/// Opcodes used for writes (`IFCONFIG0.WRITEOC`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WriteOpcode {
+    /// Single line page program, opcode `0x02`.
+    PageProgram = 0,
+    /// Dual output page program, opcode `0xA2`.
+    PP2O = 1,
+    /// Quad output page program, opcode `0x32`.
+    PP4O = 2,
+    /// Quad I/O page program, opcode `0x38`.
+    PP4IO = 3,
+}
+
+/// Number of address bytes sent to the flash device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AddressMode {
+    _24Bit = 0,
+    _32Bit = 1,
+}
+
+/// Page size of the flash device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WritePageSize {
+    _256Bytes = 0,
+    _512Bytes = 1,
+}
+
+/// Clock polarity and phase of the serial interface.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SpiMode {
+    /// Clock idles low, data is sampled on the rising edge.
+    Mode0 = 0,
+    /// Clock idles high, data is sampled on the rising edge.
+    Mode3 = 1,
+}
+
+/// QSPI errors.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// An operation was attempted on an unaligned boundary.
+    Unaligned,
+    /// An operation was attempted outside the boundaries of the flash.
+    OutOfBounds,
+    /// A custom instruction was given more than 8 bytes of data.
+    InvalidLength,
+    /// Deep power-down was not enabled in the [`Config`].
+    DeepPowerDownDisabled,
+}
+
+impl NorFlashError for Error {
+    fn kind(&self) -> NorFlashErrorKind {
+        match self {
+            Error::Unaligned => NorFlashErrorKind::NotAligned,
+            Error::OutOfBounds => NorFlashErrorKind::OutOfBounds,
+            Error::InvalidLength | Error::DeepPowerDownDisabled => NorFlashErrorKind::Other,
+        }
+    }
+}
@@ -0,0 +1,538 @@
+//! Bluetooth Low Energy radio
+//!
+//! [`Radio`] sends and receives raw link layer PDUs, on the advertising channels (37, 38 and 39)
+//! as well as on the data channels. It does not implement the link layer itself: the timing of
+//! advertising events, connection events and acknowledgments is left to the application.
+//!
+//! The PDUs are stored in RAM in the packet format expected by the [`ccm`](crate::ccm) module
+//! (`S0`, length, `S1` and payload), so the encrypted PDUs of a link can be produced and checked
+//! with [`Ccm`](crate::ccm::Ccm), using [`Pdu::raw`] and [`Pdu::raw_mut`].
+//!
+//! ```ignore
+//! let mut radio = Radio::init(p.RADIO, &clocks);
+//! let mut pdu = Pdu::advertising(AdvPduType::AdvNonconnInd, address, true, &adv_data);
+//! for &channel in ADVERTISING_CHANNELS.iter() {
+//!     radio.set_channel(channel);
+//!     radio.send(&mut pdu);
+//! }
+//! ```
+
+use core::{
+    marker::PhantomData,
+    ops,
+    sync::atomic::{compiler_fence, Ordering},
+};
+
+use crate::{
+    clocks::{Clocks, ExternalOscillator},
+    pac::RADIO,
+    timer::{self, Timer},
+};
+
+/// Transmission power
+pub use crate::pac::radio::txpower::TXPOWER_A as TxPower;
+
+/// Access address of the advertising channels
+pub const ADVERTISING_ACCESS_ADDRESS: u32 = 0x8E89_BED6;
+
+/// CRC initial value of the advertising channels
+pub const ADVERTISING_CRC_INIT: u32 = 0x55_5555;
+
+/// CRC polynomial of the link layer: x^24 + x^10 + x^9 + x^6 + x^4 + x^3 + x + 1
+pub const CRC_POLY: u32 = 0x00_065B;
+
+/// Indices of the primary advertising channels
+pub const ADVERTISING_CHANNELS: [u8; 3] = [37, 38, 39];
+
+// SHORTS bits
+const READY_START: u32 = 1 << 0;
+const END_DISABLE: u32 = 1 << 1;
+const ADDRESS_RSSISTART: u32 = 1 << 4;
+const DISABLED_RSSISTOP: u32 = 1 << 8;
+#[cfg(any(feature = "52811", feature = "52833", feature = "52840"))]
+const PHYEND_DISABLE: u32 = 1 << 20;
+
+// The transmitter is disabled once the last bit is on air, which is signalled by PHYEND on the
+// chips supporting the Coded PHY
+#[cfg(any(feature = "52811", feature = "52833", feature = "52840"))]
+const TX_DISABLE: u32 = PHYEND_DISABLE;
+#[cfg(not(any(feature = "52811", feature = "52833", feature = "52840")))]
+const TX_DISABLE: u32 = END_DISABLE;
+
+/// PHY (physical layer) used to send and receive
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Phy {
+    /// LE 1M, 1 Mbit/s
+    _1M,
+    /// LE 2M, 2 Mbit/s
+    _2M,
+    /// LE Coded, 125 kbit/s (S=8)
+    #[cfg(any(feature = "52811", feature = "52833", feature = "52840"))]
+    CodedS8,
+    /// LE Coded, 500 kbit/s (S=2)
+    ///
+    /// The receiver accepts packets coded with both S=2 and S=8 in either Coded mode.
+    #[cfg(any(feature = "52811", feature = "52833", feature = "52840"))]
+    CodedS2,
+}
+
+impl Phy {
+    /// Value of the `MODE` register
+    fn mode(self) -> u32 {
+        match self {
+            Phy::_1M => 3,
+            Phy::_2M => 4,
+            #[cfg(any(feature = "52811", feature = "52833", feature = "52840"))]
+            Phy::CodedS8 => 5,
+            #[cfg(any(feature = "52811", feature = "52833", feature = "52840"))]
+            Phy::CodedS2 => 6,
+        }
+    }
+
+    /// Value of the `PCNF0` register
+    fn pcnf0(self) -> u32 {
+        // 8-bit length field, 1 byte S0 and S1 included in RAM (but not on air), as expected by
+        // the CCM
+        let packet = 8 | 1 << 8 | 1 << 20;
+        match self {
+            // 8-bit preamble
+            Phy::_1M => packet,
+            // 16-bit preamble
+            Phy::_2M => packet | 1 << 24,
+            // long range preamble, 2-bit code indicator and 3-bit TERM1
+            #[cfg(any(feature = "52811", feature = "52833", feature = "52840"))]
+            Phy::CodedS8 | Phy::CodedS2 => packet | 3 << 24 | 2 << 22 | 3 << 29,
+        }
+    }
+}
+
+/// Bluetooth Low Energy radio
+pub struct Radio<'c> {
+    radio: RADIO,
+    // used to freeze `Clocks`
+    _clocks: PhantomData<&'c ()>,
+}
+
+impl<'c> Radio<'c> {
+    /// Initializes the radio for Bluetooth Low Energy operation
+    ///
+    /// The radio is configured for the LE 1M PHY on advertising channel 37, with the access
+    /// address and CRC initial value of the advertising channels, and data whitening enabled.
+    pub fn init<L, LSTAT>(radio: RADIO, _clocks: &'c Clocks<ExternalOscillator, L, LSTAT>) -> Self {
+        let mut radio = Self {
+            radio,
+            _clocks: PhantomData,
+        };
+
+        radio.radio.shorts.reset();
+        radio
+            .radio
+            .intenclr
+            .write(|w| unsafe { w.bits(0xffff_ffff) });
+
+        // go to a known state
+        radio.disable();
+
+        // NOTE(unsafe) radio is currently disabled
+        unsafe {
+            radio.radio.pcnf1.write(|w| {
+                w.maxlen()
+                    .bits(Pdu::CAPACITY as u8) // payload length
+                    .statlen()
+                    .bits(0) // no static length
+                    .balen()
+                    .bits(3) // 3 bytes base address + 1 byte prefix
+                    .endian()
+                    .clear_bit() // little endian
+                    .whiteen()
+                    .set_bit() // data whitening
+            });
+
+            // logical address 0 is used to both send and receive
+            radio.radio.txaddress.write(|w| w.bits(0));
+            radio.radio.rxaddresses.write(|w| w.bits(1));
+
+            // 3 bytes CRC over the PDU, not including the access address
+            radio.radio.crccnf.write(|w| w.bits(3 | 1 << 8));
+            radio.radio.crcpoly.write(|w| w.bits(CRC_POLY));
+        }
+
+        radio.set_phy(Phy::_1M);
+        radio.set_access_address(ADVERTISING_ACCESS_ADDRESS);
+        radio.set_crc_init(ADVERTISING_CRC_INIT);
+        radio.set_channel(ADVERTISING_CHANNELS[0]);
+        radio.set_txpower(TxPower::_0D_BM);
+
+        radio
+    }
+
+    /// Changes the PHY
+    pub fn set_phy(&mut self, phy: Phy) {
+        // NOTE(unsafe) radio is disabled between operations
+        unsafe {
+            self.radio.mode.write(|w| w.bits(phy.mode()));
+            self.radio.pcnf0.write(|w| w.bits(phy.pcnf0()));
+        }
+    }
+
+    /// Changes the access address
+    pub fn set_access_address(&mut self, access_address: u32) {
+        // NOTE(unsafe) radio is disabled between operations
+        unsafe {
+            self.radio.base0.write(|w| w.bits(access_address << 8));
+            self.radio
+                .prefix0
+                .write(|w| w.ap0().bits((access_address >> 24) as u8));
+        }
+    }
+
+    /// Changes the CRC polynomial
+    ///
+    /// The polynomial of the link layer, [`CRC_POLY`], is used by default.
+    pub fn set_crc_poly(&mut self, poly: u32) {
+        self.radio.crcpoly.write(|w| unsafe { w.bits(poly) });
+    }
+
+    /// Changes the CRC initial value
+    ///
+    /// [`ADVERTISING_CRC_INIT`] is used on the advertising channels, while connections use the
+    /// value exchanged in the connection request.
+    pub fn set_crc_init(&mut self, init: u32) {
+        self.radio
+            .crcinit
+            .write(|w| unsafe { w.bits(init & 0xff_ffff) });
+    }
+
+    /// Enables or disables data whitening
+    ///
+    /// Whitening is always used by the link layer, and enabled by default. The whitening is
+    /// initialized from the channel index, set by [`set_channel`](Self::set_channel).
+    pub fn set_whitening(&mut self, enabled: bool) {
+        self.radio.pcnf1.modify(|_, w| w.whiteen().bit(enabled));
+    }
+
+    /// Changes the channel, from its link layer index
+    ///
+    /// Channels 0 to 36 are the data channels, and 37, 38 and 39 the primary advertising channels.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `index` is larger than 39
+    pub fn set_channel(&mut self, index: u8) {
+        let frequency = match index {
+            0..=10 => 4 + 2 * index,
+            11..=36 => 28 + 2 * (index - 11),
+            37 => 2,
+            38 => 26,
+            39 => 80,
+            _ => panic!("invalid channel index {}", index),
+        };
+
+        // NOTE(unsafe) radio is disabled between operations
+        unsafe {
+            // offset from 2400 MHz
+            self.radio.frequency.write(|w| w.bits(frequency.into()));
+            self.radio
+                .datawhiteiv
+                .write(|w| w.bits(u32::from(index) | 0x40));
+        }
+    }
+
+    /// Changes the TX power
+    pub fn set_txpower(&mut self, power: TxPower) {
+        self.radio.txpower.write(|w| w.txpower().variant(power));
+    }
+
+    /// Returns the RSSI of the last received packet, in dBm
+    pub fn rssi(&self) -> i8 {
+        -((self.radio.rssisample.read().bits() & 0x7f) as i8)
+    }
+
+    /// Sends the given `pdu`
+    ///
+    /// NOTE this method will *not* modify the `pdu` argument. The mutable reference is used to
+    /// ensure the `pdu` buffer is allocated in RAM, which is required by the RADIO peripheral
+    pub fn send(&mut self, pdu: &mut Pdu) {
+        self.start(pdu, TX_DISABLE);
+        self.radio.tasks_txen.write(|w| unsafe { w.bits(1) });
+        self.wait_disabled();
+    }
+
+    /// Receives one PDU into the given `pdu` buffer
+    ///
+    /// This method returns the `Ok` variant if the CRC of the packet was successfully validated
+    /// by the hardware; otherwise it returns `Error::Crc`. In either case, `pdu` will be updated
+    /// with the received packet's data.
+    pub fn recv(&mut self, pdu: &mut Pdu) -> Result<(), Error> {
+        self.start(pdu, END_DISABLE | ADDRESS_RSSISTART | DISABLED_RSSISTOP);
+        self.radio.tasks_rxen.write(|w| unsafe { w.bits(1) });
+        self.wait_disabled();
+        self.crc_status()
+    }
+
+    /// Listens for a PDU for no longer than the specified amount of microseconds and copies its
+    /// contents into the given `pdu` buffer
+    ///
+    /// If no packet is received within the specified time then the `Timeout` error is returned. A
+    /// packet whose reception started before the timeout is received entirely.
+    pub fn recv_timeout<I>(
+        &mut self,
+        pdu: &mut Pdu,
+        timer: &mut Timer<I>,
+        microseconds: u32,
+    ) -> Result<(), Error>
+    where
+        I: timer::Instance,
+    {
+        timer.start(microseconds);
+
+        self.start(pdu, END_DISABLE | ADDRESS_RSSISTART | DISABLED_RSSISTOP);
+        self.radio.events_address.reset();
+        self.radio.tasks_rxen.write(|w| unsafe { w.bits(1) });
+
+        loop {
+            if self.radio.events_disabled.read().bits() != 0 {
+                self.wait_disabled();
+                return self.crc_status();
+            }
+
+            if self.radio.events_address.read().bits() == 0 && timer.reset_if_finished() {
+                self.disable();
+                self.radio.shorts.reset();
+                compiler_fence(Ordering::Acquire);
+                return Err(Error::Timeout);
+            }
+        }
+    }
+
+    /// Returns the RADIO peripheral
+    pub fn free(mut self) -> RADIO {
+        self.disable();
+        self.radio.shorts.reset();
+        self.radio
+    }
+
+    /// Points the RADIO to `pdu` and enables `shorts`, before the TXEN or RXEN task
+    fn start(&mut self, pdu: &mut Pdu, shorts: u32) {
+        self.disable();
+
+        // NOTE(unsafe) radio is disabled, so the DMA transfer has not yet started
+        unsafe {
+            self.radio
+                .packetptr
+                .write(|w| w.bits(pdu.buffer.as_mut_ptr() as u32));
+            self.radio.shorts.write(|w| w.bits(READY_START | shorts));
+        }
+
+        // the DMA transfer will start at some point after the following write operation to the
+        // TXEN or RXEN task so we place the compiler fence here
+        compiler_fence(Ordering::Release);
+    }
+
+    /// Waits for the operation started with `start` to end
+    fn wait_disabled(&mut self) {
+        while self.radio.events_disabled.read().bits() == 0 {}
+        self.radio.events_disabled.reset();
+        self.radio.shorts.reset();
+        compiler_fence(Ordering::Acquire);
+    }
+
+    fn crc_status(&self) -> Result<(), Error> {
+        if self.radio.crcstatus.read().bits() & 1 == 1 {
+            Ok(())
+        } else {
+            Err(Error::Crc)
+        }
+    }
+
+    /// Moves the radio from any state to the DISABLED state
+    fn disable(&mut self) {
+        // STATE is 0 in the DISABLED state
+        if self.radio.state.read().bits() != 0 {
+            self.radio.events_disabled.reset();
+            self.radio.tasks_disable.write(|w| unsafe { w.bits(1) });
+            while self.radio.events_disabled.read().bits() == 0 {}
+        }
+        self.radio.events_disabled.reset();
+    }
+}
+
+/// Error
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// Incorrect CRC
+    Crc,
+    /// Timeout
+    Timeout,
+}
+
+/// PDU types of the advertising channels, stored in the header of the PDU
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AdvPduType {
+    /// Connectable and scannable undirected advertising
+    AdvInd = 0,
+    /// Connectable directed advertising
+    AdvDirectInd = 1,
+    /// Non-connectable and non-scannable undirected advertising, used by beacons
+    AdvNonconnInd = 2,
+    /// Scan request
+    ScanReq = 3,
+    /// Scan response
+    ScanRsp = 4,
+    /// Connection request
+    ConnectInd = 5,
+    /// Scannable undirected advertising
+    AdvScanInd = 6,
+    /// Extended advertising, the only advertising PDU allowed on the coded PHY
+    AdvExtInd = 7,
+}
+
+/// A link layer PDU
+///
+/// The `Pdu` is made of a header, with the PDU type and flags (the `S0` field of the RADIO),
+/// followed by the payload, whose length is stored in the second header byte. The CRC is
+/// computed in hardware, and never copied to or from RAM.
+///
+/// The API lets users modify the payload via the `deref` and `copy_from_slice` methods. These
+/// methods will automatically update the length.
+pub struct Pdu {
+    buffer: [u8; Self::SIZE],
+}
+
+impl Default for Pdu {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Pdu {
+    // for indexing purposes
+    const HEADER: usize = 0;
+    const LENGTH: usize = 1;
+    // S1 byte, not sent on air, is at 2
+    const PAYLOAD: usize = 3;
+
+    /// Maximum payload a single PDU can contain, in bytes
+    pub const CAPACITY: usize = 255;
+    const SIZE: usize = Self::PAYLOAD + Self::CAPACITY;
+
+    /// Maximum data a legacy advertising PDU can carry after the address, in bytes
+    pub const LEGACY_ADV_DATA_CAPACITY: usize = 31;
+
+    /// Returns an empty PDU (length = 0) with the given `header` byte
+    pub fn new(header: u8) -> Self {
+        let mut pdu = Self {
+            buffer: [0; Self::SIZE],
+        };
+        pdu.set_header(header);
+        pdu
+    }
+
+    /// Returns an advertising channel PDU, with the `address` of the advertiser (or the scanner,
+    /// for `ScanReq` and `ConnectInd`) followed by `data`
+    ///
+    /// `random_address` sets the TxAdd flag of the header, telling if `address` is a random
+    /// address or a public one.
+    ///
+    /// Only legacy PDUs can be built this way. The payload of an `AdvExtInd` PDU starts with an
+    /// extended header, and has to be filled with `copy_from_slice` instead.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `pdu_type` is `AdvExtInd`, or if `data` is larger than
+    /// `Self::LEGACY_ADV_DATA_CAPACITY`
+    pub fn advertising(
+        pdu_type: AdvPduType,
+        address: [u8; 6],
+        random_address: bool,
+        data: &[u8],
+    ) -> Self {
+        assert!(pdu_type != AdvPduType::AdvExtInd);
+        assert!(data.len() <= Self::LEGACY_ADV_DATA_CAPACITY);
+        let mut pdu = Self::new(pdu_type as u8 | u8::from(random_address) << 6);
+        pdu.set_len(address.len() + data.len());
+        pdu[..6].copy_from_slice(&address);
+        pdu[6..].copy_from_slice(data);
+        pdu
+    }
+
+    /// Returns the header byte, with the PDU type and flags
+    pub fn header(&self) -> u8 {
+        self.buffer[Self::HEADER]
+    }
+
+    /// Changes the header byte
+    pub fn set_header(&mut self, header: u8) {
+        self.buffer[Self::HEADER] = header;
+    }
+
+    /// Returns the type of an advertising channel PDU, or `None` for a reserved type
+    pub fn adv_pdu_type(&self) -> Option<AdvPduType> {
+        Some(match self.header() & 0xf {
+            0 => AdvPduType::AdvInd,
+            1 => AdvPduType::AdvDirectInd,
+            2 => AdvPduType::AdvNonconnInd,
+            3 => AdvPduType::ScanReq,
+            4 => AdvPduType::ScanRsp,
+            5 => AdvPduType::ConnectInd,
+            6 => AdvPduType::AdvScanInd,
+            7 => AdvPduType::AdvExtInd,
+            _ => return None,
+        })
+    }
+
+    /// Fills the payload with given `src` data
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `src` is larger than `Self::CAPACITY`
+    pub fn copy_from_slice(&mut self, src: &[u8]) {
+        self.set_len(src.len());
+        self.buffer[Self::PAYLOAD..][..src.len()].copy_from_slice(src);
+    }
+
+    /// Returns the size of the payload
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.buffer[Self::LENGTH].into()
+    }
+
+    /// Changes the size of the payload
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `len` is larger than `Self::CAPACITY`
+    pub fn set_len(&mut self, len: usize) {
+        assert!(len <= Self::CAPACITY);
+        self.buffer[Self::LENGTH] = len as u8;
+    }
+
+    /// Returns the PDU in the packet format of the [`ccm`](crate::ccm) module, to be encrypted
+    pub fn raw(&self) -> &[u8] {
+        &self.buffer[..Self::PAYLOAD + self.len()]
+    }
+
+    /// Returns the whole PDU buffer in the packet format of the [`ccm`](crate::ccm) module, to
+    /// receive an encrypted or decrypted packet
+    pub fn raw_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer
+    }
+}
+
+impl ops::Deref for Pdu {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buffer[Self::PAYLOAD..][..self.len()]
+    }
+}
+
+impl ops::DerefMut for Pdu {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        let len = self.len();
+        &mut self.buffer[Self::PAYLOAD..][..len]
+    }
+}
@@ -0,0 +1,794 @@
+//! Enhanced ShockBurst radio protocol
+//!
+//! ESB is a packet protocol with automatic acknowledgments and retransmissions, used by Nordic
+//! devices and compatible with the nRF24L01+ when using dynamic payload lengths. A link is made
+//! of a primary transmitter (PTX), sending packets on one of eight pipes, and a primary receiver
+//! (PRX), acknowledging them. The PRX can attach a payload to its acknowledgments, to send data
+//! back to the PTX.
+//!
+//! [`Esb`] is driven by the RADIO and TIMER interrupts: it is typically shared between the
+//! application and the interrupt handlers in a `Mutex`, with [`Esb::on_radio_interrupt`] and
+//! [`Esb::on_timer_interrupt`] called from the handlers. The turnaround between transmission and
+//! reception is performed by RADIO shortcuts, and the acknowledgment timeout by the TIMER,
+//! started and stopped by PPI channels.
+//!
+//! ```ignore
+//! static mut BUFFERS: esb::Buffers = esb::Buffers::new();
+//!
+//! let ppi = Parts::new(p.PPI);
+//! let channels = PpiChannels { timer_start: ppi.ppi0, timer_stop: ppi.ppi1, timeout: ppi.ppi2 };
+//! let mut esb = Esb::new(p.RADIO, p.TIMER0, channels, unsafe { &mut BUFFERS }, &clocks,
+//!     Config::default(), &Addresses::default());
+//! // with the RADIO and TIMER0 interrupts unmasked, calling the `on_*_interrupt` methods
+//! esb.write_payload(b"hello", 0, true)?;
+//! ```
+
+use core::{
+    marker::PhantomData,
+    ops,
+    sync::atomic::{compiler_fence, Ordering},
+};
+
+use crate::{
+    clocks::{Clocks, ExternalOscillator},
+    pac::RADIO,
+    ppi::{ConfigurablePpi, Ppi},
+    timer,
+};
+
+pub use super::ble::TxPower;
+
+/// Maximum payload length, compatible with the nRF24L01+
+pub const MAX_PAYLOAD_LEN: usize = 32;
+
+/// Number of packets in the TX and RX FIFOs
+pub const FIFO_DEPTH: usize = 3;
+
+/// Length field, `S1` field (PID and acknowledgment flag) and payload
+const BUFFER_SIZE: usize = 2 + MAX_PAYLOAD_LEN;
+
+// SHORTS bits
+const READY_START: u32 = 1 << 0;
+const END_DISABLE: u32 = 1 << 1;
+const DISABLED_TXEN: u32 = 1 << 2;
+const DISABLED_RXEN: u32 = 1 << 3;
+const ADDRESS_RSSISTART: u32 = 1 << 4;
+const DISABLED_RSSISTOP: u32 = 1 << 8;
+
+const TX_SHORTS: u32 = READY_START | END_DISABLE;
+const RX_SHORTS: u32 = READY_START | END_DISABLE | ADDRESS_RSSISTART | DISABLED_RSSISTOP;
+
+/// Role of the device on the link
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Role {
+    /// Primary transmitter
+    Ptx,
+    /// Primary receiver
+    Prx,
+}
+
+/// On air data rate
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Bitrate {
+    _1Mbit = 0,
+    _2Mbit = 1,
+}
+
+/// CRC length
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Crc {
+    _8Bit,
+    _16Bit,
+}
+
+/// ESB configuration
+#[derive(Debug, Copy, Clone)]
+pub struct Config {
+    pub role: Role,
+    pub bitrate: Bitrate,
+    pub crc: Crc,
+    pub tx_power: TxPower,
+    /// RF channel, from 0 to 100: the frequency is 2400 MHz + `channel` MHz
+    pub channel: u8,
+    /// Delay between retransmissions, in microseconds
+    pub retransmit_delay: u16,
+    /// Number of retransmissions of a packet that is not acknowledged, before it is dropped
+    pub retransmit_count: u8,
+    /// Time the PTX waits for the address of an acknowledgment once its receiver is ready, in
+    /// microseconds
+    pub ack_timeout: u16,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            role: Role::Ptx,
+            bitrate: Bitrate::_2Mbit,
+            crc: Crc::_16Bit,
+            tx_power: TxPower::_0D_BM,
+            channel: 2,
+            retransmit_delay: 600,
+            retransmit_count: 3,
+            ack_timeout: 120,
+        }
+    }
+}
+
+/// Addresses of the pipes
+///
+/// The address of pipe 0 is `base0` followed by `prefixes[0]`, and the address of pipe `n` is
+/// `base1` followed by `prefixes[n]`. With addresses shorter than 5 bytes, the first bytes of
+/// the base addresses are used.
+#[derive(Debug, Copy, Clone)]
+pub struct Addresses {
+    pub base0: [u8; 4],
+    pub base1: [u8; 4],
+    pub prefixes: [u8; 8],
+    /// Address length, from 3 to 5 bytes
+    pub length: u8,
+    /// Bit mask of the pipes the PRX receives on
+    pub rx_pipes: u8,
+}
+
+impl Default for Addresses {
+    fn default() -> Self {
+        Self {
+            base0: [0xe7; 4],
+            base1: [0xc2; 4],
+            prefixes: [0xe7, 0xc2, 0xc3, 0xc4, 0xc5, 0xc6, 0xc7, 0xc8],
+            length: 5,
+            rx_pipes: 0xff,
+        }
+    }
+}
+
+/// PPI channels used by [`Esb`] for the acknowledgment timeout
+pub struct PpiChannels<A, B, C> {
+    /// Starts the TIMER when the receiver is ready
+    pub timer_start: A,
+    /// Stops the TIMER when an address is received
+    pub timer_stop: B,
+    /// Disables the RADIO when the TIMER expires
+    pub timeout: C,
+}
+
+/// Packet buffers used by the RADIO
+///
+/// They must outlive the transfers, so they are provided as a `&'static mut`.
+pub struct Buffers {
+    tx: [u8; BUFFER_SIZE],
+    rx: [u8; BUFFER_SIZE],
+}
+
+impl Default for Buffers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Buffers {
+    /// Returns zeroed buffers, usable to initialize a `static`
+    pub const fn new() -> Self {
+        Self {
+            tx: [0; BUFFER_SIZE],
+            rx: [0; BUFFER_SIZE],
+        }
+    }
+}
+
+/// A packet of the TX or RX FIFO
+#[derive(Copy, Clone)]
+pub struct Packet {
+    data: [u8; MAX_PAYLOAD_LEN],
+    len: u8,
+    pipe: u8,
+    ack: bool,
+    rssi: i8,
+}
+
+impl Packet {
+    const EMPTY: Packet = Packet {
+        data: [0; MAX_PAYLOAD_LEN],
+        len: 0,
+        pipe: 0,
+        ack: false,
+        rssi: 0,
+    };
+
+    fn new(payload: &[u8], pipe: u8, ack: bool, rssi: i8) -> Self {
+        let mut packet = Self::EMPTY;
+        packet.data[..payload.len()].copy_from_slice(payload);
+        packet.len = payload.len() as u8;
+        packet.pipe = pipe;
+        packet.ack = ack;
+        packet.rssi = rssi;
+        packet
+    }
+
+    /// Returns the pipe the packet was received on
+    pub fn pipe(&self) -> u8 {
+        self.pipe
+    }
+
+    /// Returns `true` if the transmitter of the packet requested an acknowledgment
+    pub fn ack_requested(&self) -> bool {
+        self.ack
+    }
+
+    /// Returns the RSSI of the packet, in dBm
+    pub fn rssi(&self) -> i8 {
+        self.rssi
+    }
+}
+
+impl ops::Deref for Packet {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.data[..usize::from(self.len)]
+    }
+}
+
+struct Fifo {
+    packets: [Packet; FIFO_DEPTH],
+    start: usize,
+    len: usize,
+}
+
+impl Fifo {
+    fn new() -> Self {
+        Self {
+            packets: [Packet::EMPTY; FIFO_DEPTH],
+            start: 0,
+            len: 0,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == FIFO_DEPTH
+    }
+
+    fn push(&mut self, packet: Packet) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        self.packets[(self.start + self.len) % FIFO_DEPTH] = packet;
+        self.len += 1;
+        true
+    }
+
+    fn peek(&self) -> Option<&Packet> {
+        if self.len == 0 {
+            None
+        } else {
+            Some(&self.packets[self.start])
+        }
+    }
+
+    fn pop(&mut self) -> Option<Packet> {
+        let packet = *self.peek()?;
+        self.start = (self.start + 1) % FIFO_DEPTH;
+        self.len -= 1;
+        Some(packet)
+    }
+
+    /// Removes the first packet for `pipe`
+    fn remove_pipe(&mut self, pipe: u8) -> Option<Packet> {
+        let index =
+            (0..self.len).find(|i| self.packets[(self.start + i) % FIFO_DEPTH].pipe == pipe)?;
+        let packet = self.packets[(self.start + index) % FIFO_DEPTH];
+        for i in index..self.len - 1 {
+            self.packets[(self.start + i) % FIFO_DEPTH] =
+                self.packets[(self.start + i + 1) % FIFO_DEPTH];
+        }
+        self.len -= 1;
+        Some(packet)
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+/// Event reported by [`Esb::on_radio_interrupt`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Event {
+    /// The PTX sent a packet, and received its acknowledgment if it requested one
+    TxSuccess,
+    /// The PTX dropped a packet that was not acknowledged after all the retransmissions
+    TxFailed,
+    /// A packet was added to the RX FIFO
+    Received,
+}
+
+/// Error
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// The payload is longer than `MAX_PAYLOAD_LEN`
+    PayloadTooLong,
+    /// The TX FIFO is full
+    TxFifoFull,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum State {
+    Idle,
+    /// PTX sending the packet at the head of the TX FIFO
+    Tx,
+    /// PTX waiting for the acknowledgment
+    WaitAck,
+    /// PTX waiting before retransmitting
+    RetransmitDelay,
+    /// PRX receiving
+    Rx,
+    /// PRX sending an acknowledgment
+    SendAck,
+    /// PRX aborting the turnaround to send an acknowledgment, to receive again
+    RestartRx,
+}
+
+/// Enhanced ShockBurst driver
+pub struct Esb<'c, T, A, B, C> {
+    radio: RADIO,
+    timer: T,
+    ppi: PpiChannels<A, B, C>,
+    buffers: &'static mut Buffers,
+    config: Config,
+    rx_pipes: u8,
+    state: State,
+    tx_fifo: Fifo,
+    rx_fifo: Fifo,
+    // PTX packet identifier, incremented for every new packet
+    pid: u8,
+    retransmits_left: u8,
+    // PRX identifier and CRC of the last packet received on each pipe, to drop retransmissions
+    last_rx: [Option<(u8, u16)>; 8],
+    // used to freeze `Clocks`
+    _clocks: PhantomData<&'c ()>,
+}
+
+impl<'c, T, A, B, C> Esb<'c, T, A, B, C>
+where
+    T: timer::Instance,
+    A: ConfigurablePpi,
+    B: ConfigurablePpi,
+    C: ConfigurablePpi,
+{
+    /// Initializes the RADIO for ESB, with the given role, configuration and addresses
+    ///
+    /// The RADIO interrupt is enabled in the peripheral, and the TIMER interrupt is enabled when
+    /// needed, but both have to be unmasked in the NVIC.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `config.channel` is larger than 100, or `addresses.length` is not
+    /// between 3 and 5
+    pub fn new<L, LSTAT>(
+        radio: RADIO,
+        timer: T,
+        mut ppi: PpiChannels<A, B, C>,
+        buffers: &'static mut Buffers,
+        _clocks: &'c Clocks<ExternalOscillator, L, LSTAT>,
+        config: Config,
+        addresses: &Addresses,
+    ) -> Self {
+        assert!(config.channel <= 100);
+        assert!(addresses.length >= 3 && addresses.length <= 5);
+
+        radio.shorts.reset();
+        radio.intenclr.write(|w| unsafe { w.bits(0xffff_ffff) });
+        // STATE is 0 in the DISABLED state
+        if radio.state.read().bits() != 0 {
+            radio.tasks_disable.write(|w| unsafe { w.bits(1) });
+            while radio.events_disabled.read().bits() == 0 {}
+        }
+        radio.events_disabled.reset();
+
+        let (crc_len, crc_init, crc_poly) = match config.crc {
+            Crc::_8Bit => (1, 0xff, 0x107),
+            Crc::_16Bit => (2, 0xffff, 0x11021),
+        };
+
+        // NOTE(unsafe) radio is currently disabled
+        unsafe {
+            radio.mode.write(|w| w.bits(config.bitrate as u32));
+            // 6-bit length field and 3-bit S1 field (PID and acknowledgment flag), 8-bit preamble
+            radio.pcnf0.write(|w| w.bits(6 | 3 << 16));
+            // maximum payload length, base address length, big endian, no whitening
+            radio.pcnf1.write(|w| {
+                w.bits(MAX_PAYLOAD_LEN as u32 | u32::from(addresses.length - 1) << 16 | 1 << 24)
+            });
+            // the CRC covers the address
+            radio.crccnf.write(|w| w.bits(crc_len));
+            radio.crcinit.write(|w| w.bits(crc_init));
+            radio.crcpoly.write(|w| w.bits(crc_poly));
+            radio.frequency.write(|w| w.bits(config.channel.into()));
+
+            // the bits of each address byte are sent in the order of the nRF24L01+
+            // with addresses shorter than 5 bytes, the radio ignores the low bytes of BASEn, which
+            // hold the last bytes of the base address
+            let base = |bytes: [u8; 4]| u32::from_le_bytes(bytes).reverse_bits();
+            let prefix = |bytes: &[u8]| {
+                let mut prefix = [0; 4];
+                prefix.copy_from_slice(bytes);
+                u32::from_le_bytes(prefix).reverse_bits().swap_bytes()
+            };
+            radio.base0.write(|w| w.bits(base(addresses.base0)));
+            radio.base1.write(|w| w.bits(base(addresses.base1)));
+            radio
+                .prefix0
+                .write(|w| w.bits(prefix(&addresses.prefixes[..4])));
+            radio
+                .prefix1
+                .write(|w| w.bits(prefix(&addresses.prefixes[4..])));
+
+            // DISABLED
+            radio.intenset.write(|w| w.bits(1 << 4));
+        }
+        radio
+            .txpower
+            .write(|w| w.txpower().variant(config.tx_power));
+
+        timer.timer_cancel();
+        timer.disable_interrupt();
+        timer.set_oneshot();
+
+        ppi.timer_start.set_event_endpoint(&radio.events_ready);
+        ppi.timer_start
+            .set_task_endpoint(&timer.as_timer0().tasks_start);
+        ppi.timer_stop.set_event_endpoint(&radio.events_address);
+        ppi.timer_stop
+            .set_task_endpoint(&timer.as_timer0().tasks_stop);
+        ppi.timeout
+            .set_event_endpoint(&timer.as_timer0().events_compare[0]);
+        ppi.timeout.set_task_endpoint(&radio.tasks_disable);
+
+        Self {
+            radio,
+            timer,
+            ppi,
+            buffers,
+            config,
+            rx_pipes: addresses.rx_pipes,
+            state: State::Idle,
+            tx_fifo: Fifo::new(),
+            rx_fifo: Fifo::new(),
+            pid: 0,
+            retransmits_left: config.retransmit_count,
+            last_rx: [None; 8],
+            _clocks: PhantomData,
+        }
+    }
+
+    /// Adds a packet to the TX FIFO
+    ///
+    /// For a PTX, the packet is sent on `pipe`, and acknowledged if `ack` is `true`. The
+    /// transmission starts immediately if the PTX is idle.
+    ///
+    /// For a PRX, the packet is the payload of the acknowledgment of the next packet received on
+    /// `pipe`, and `ack` is ignored.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `pipe` is larger than 7
+    pub fn write_payload(&mut self, payload: &[u8], pipe: u8, ack: bool) -> Result<(), Error> {
+        assert!(pipe < 8);
+        if payload.len() > MAX_PAYLOAD_LEN {
+            return Err(Error::PayloadTooLong);
+        }
+        if !self.tx_fifo.push(Packet::new(payload, pipe, ack, 0)) {
+            return Err(Error::TxFifoFull);
+        }
+        if self.config.role == Role::Ptx && self.state == State::Idle {
+            self.start_tx();
+        }
+        Ok(())
+    }
+
+    /// Removes the oldest packet from the RX FIFO
+    ///
+    /// The PRX receives the packets sent by the PTX, and the PTX the payloads of the
+    /// acknowledgments.
+    pub fn read_payload(&mut self) -> Option<Packet> {
+        self.rx_fifo.pop()
+    }
+
+    /// Removes all the packets from the TX FIFO that are not being sent
+    pub fn flush_tx(&mut self) {
+        let sending = match self.state {
+            State::Tx | State::WaitAck | State::RetransmitDelay => self.tx_fifo.pop(),
+            _ => None,
+        };
+        self.tx_fifo.clear();
+        if let Some(packet) = sending {
+            self.tx_fifo.push(packet);
+        }
+    }
+
+    /// Removes all the packets from the RX FIFO
+    pub fn flush_rx(&mut self) {
+        self.rx_fifo.clear();
+    }
+
+    /// Returns `true` if the PTX has sent all the packets of the TX FIFO, or the PRX is not
+    /// receiving
+    pub fn is_idle(&self) -> bool {
+        self.state == State::Idle
+    }
+
+    /// Starts receiving on the pipes of `Addresses::rx_pipes`
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the device is not a PRX
+    pub fn start_rx(&mut self) {
+        assert_eq!(self.config.role, Role::Prx);
+        if self.state != State::Idle {
+            return;
+        }
+
+        // NOTE(unsafe) radio is disabled, so the DMA transfer has not yet started
+        unsafe {
+            self.radio
+                .rxaddresses
+                .write(|w| w.bits(self.rx_pipes.into()));
+            self.radio
+                .packetptr
+                .write(|w| w.bits(self.buffers.rx.as_mut_ptr() as u32));
+            self.radio
+                .shorts
+                .write(|w| w.bits(RX_SHORTS | DISABLED_TXEN));
+        }
+        self.state = State::Rx;
+
+        compiler_fence(Ordering::Release);
+        self.radio.tasks_rxen.write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Stops receiving
+    pub fn stop_rx(&mut self) {
+        if self.config.role == Role::Prx {
+            self.state = State::Idle;
+            self.disable();
+        }
+    }
+
+    /// Handles the RADIO interrupt, and returns the event that occurred, if any
+    pub fn on_radio_interrupt(&mut self) -> Option<Event> {
+        if self.radio.events_disabled.read().bits() == 0 {
+            return None;
+        }
+        self.radio.events_disabled.reset();
+        compiler_fence(Ordering::Acquire);
+
+        match self.state {
+            State::Idle | State::RetransmitDelay => None,
+            State::Tx => {
+                if !self.tx_fifo.peek().map_or(false, |packet| packet.ack) {
+                    return Some(self.tx_done(Event::TxSuccess));
+                }
+
+                // the receiver is ramping up through the DISABLED_RXEN shortcut, and the
+                // acknowledgment timeout starts once it is ready
+                self.radio.events_end.reset();
+                // NOTE(unsafe) the receiver is not started before READY
+                unsafe {
+                    self.radio
+                        .packetptr
+                        .write(|w| w.bits(self.buffers.rx.as_mut_ptr() as u32));
+                    self.radio.shorts.write(|w| w.bits(RX_SHORTS));
+                    let timer = self.timer.as_timer0();
+                    timer.cc[0].write(|w| w.bits(self.config.ack_timeout.into()));
+                    timer.tasks_clear.write(|w| w.bits(1));
+                }
+                self.timer.timer_reset_event();
+                self.ppi.timer_start.enable();
+                self.ppi.timer_stop.enable();
+                self.ppi.timeout.enable();
+                self.state = State::WaitAck;
+                None
+            }
+            State::WaitAck => {
+                self.ppi.timer_start.disable();
+                self.ppi.timer_stop.disable();
+                self.ppi.timeout.disable();
+                self.timer.timer_cancel();
+
+                if self.radio.events_end.read().bits() != 0 && self.crc_ok() {
+                    let len = usize::from(self.buffers.rx[0]).min(MAX_PAYLOAD_LEN);
+                    if len > 0 {
+                        let pipe = self.radio.txaddress.read().bits() as u8;
+                        let packet =
+                            Packet::new(&self.buffers.rx[2..][..len], pipe, false, self.rssi());
+                        self.rx_fifo.push(packet);
+                    }
+                    Some(self.tx_done(Event::TxSuccess))
+                } else if self.retransmits_left > 0 {
+                    self.retransmits_left -= 1;
+                    self.state = State::RetransmitDelay;
+                    self.timer
+                        .timer_start(u32::from(self.config.retransmit_delay));
+                    self.timer.enable_interrupt();
+                    None
+                } else {
+                    Some(self.tx_done(Event::TxFailed))
+                }
+            }
+            State::Rx => self.on_rx(),
+            State::SendAck | State::RestartRx => {
+                if self.state == State::SendAck {
+                    // NOTE(unsafe) the receiver is not started before READY
+                    unsafe {
+                        self.radio
+                            .packetptr
+                            .write(|w| w.bits(self.buffers.rx.as_mut_ptr() as u32));
+                    }
+                }
+                // the receiver is ramping up through the DISABLED_RXEN shortcut
+                self.radio
+                    .shorts
+                    .write(|w| unsafe { w.bits(RX_SHORTS | DISABLED_TXEN) });
+                self.state = State::Rx;
+                None
+            }
+        }
+    }
+
+    /// Handles the TIMER interrupt, used for the delay between retransmissions
+    pub fn on_timer_interrupt(&mut self) {
+        self.timer.timer_reset_event();
+        self.timer.disable_interrupt();
+        if self.state == State::RetransmitDelay {
+            self.start_tx();
+        }
+    }
+
+    /// Returns the peripherals and buffers
+    pub fn free(mut self) -> (RADIO, T, PpiChannels<A, B, C>, &'static mut Buffers) {
+        self.state = State::Idle;
+        self.ppi.timer_start.disable();
+        self.ppi.timer_stop.disable();
+        self.ppi.timeout.disable();
+        self.timer.timer_cancel();
+        self.timer.disable_interrupt();
+        self.disable();
+        self.radio
+            .intenclr
+            .write(|w| unsafe { w.bits(0xffff_ffff) });
+        (self.radio, self.timer, self.ppi, self.buffers)
+    }
+
+    /// Sends the packet at the head of the TX FIFO, if any
+    fn start_tx(&mut self) {
+        let packet = match self.tx_fifo.peek() {
+            Some(packet) => *packet,
+            None => {
+                self.state = State::Idle;
+                return;
+            }
+        };
+
+        let len = usize::from(packet.len);
+        let buffer = &mut self.buffers.tx;
+        buffer[0] = packet.len;
+        // the bit is set when an acknowledgment is requested, as the inverted NO_ACK flag of the
+        // nRF24L01+
+        buffer[1] = self.pid << 1 | u8::from(packet.ack);
+        buffer[2..][..len].copy_from_slice(&packet);
+
+        let shorts = if packet.ack {
+            TX_SHORTS | DISABLED_RXEN
+        } else {
+            TX_SHORTS
+        };
+        // NOTE(unsafe) radio is disabled, so the DMA transfer has not yet started
+        unsafe {
+            self.radio.txaddress.write(|w| w.bits(packet.pipe.into()));
+            self.radio.rxaddresses.write(|w| w.bits(1 << packet.pipe));
+            self.radio
+                .packetptr
+                .write(|w| w.bits(buffer.as_mut_ptr() as u32));
+            self.radio.shorts.write(|w| w.bits(shorts));
+        }
+        self.state = State::Tx;
+
+        compiler_fence(Ordering::Release);
+        self.radio.tasks_txen.write(|w| unsafe { w.bits(1) });
+    }
+
+    /// Removes the packet that was sent from the TX FIFO, and sends the next one
+    fn tx_done(&mut self, event: Event) -> Event {
+        self.tx_fifo.pop();
+        self.pid = (self.pid + 1) % 4;
+        self.retransmits_left = self.config.retransmit_count;
+        self.start_tx();
+        event
+    }
+
+    /// Handles a packet received by the PRX, while the transmitter is ramping up through the
+    /// DISABLED_TXEN shortcut
+    fn on_rx(&mut self) -> Option<Event> {
+        if !self.crc_ok() {
+            self.restart_rx();
+            return None;
+        }
+
+        let pipe = self.radio.rxmatch.read().bits() as u8;
+        let len = usize::from(self.buffers.rx[0]).min(MAX_PAYLOAD_LEN);
+        let s1 = self.buffers.rx[1];
+        let ack = s1 & 1 == 1;
+        let id = (s1 >> 1 & 0x3, self.radio.rxcrc.read().bits() as u16);
+
+        let mut event = None;
+        // retransmissions of a packet whose acknowledgment was lost are acknowledged again, but
+        // not added to the RX FIFO
+        if self.last_rx[usize::from(pipe)] != Some(id) {
+            let packet = Packet::new(&self.buffers.rx[2..][..len], pipe, ack, self.rssi());
+            if !self.rx_fifo.push(packet) {
+                // not acknowledged, so that the PTX retransmits it
+                self.restart_rx();
+                return None;
+            }
+            self.last_rx[usize::from(pipe)] = Some(id);
+            event = Some(Event::Received);
+        }
+
+        if !ack {
+            self.restart_rx();
+            return event;
+        }
+
+        let buffer = &mut self.buffers.tx;
+        buffer[1] = s1;
+        match self.tx_fifo.remove_pipe(pipe) {
+            Some(payload) => {
+                buffer[0] = payload.len;
+                buffer[2..][..payload.len()].copy_from_slice(&payload);
+            }
+            None => buffer[0] = 0,
+        }
+        // NOTE(unsafe) the transmitter is not started before READY
+        unsafe {
+            self.radio.txaddress.write(|w| w.bits(pipe.into()));
+            self.radio
+                .packetptr
+                .write(|w| w.bits(buffer.as_mut_ptr() as u32));
+            self.radio
+                .shorts
+                .write(|w| w.bits(TX_SHORTS | DISABLED_RXEN));
+        }
+        self.state = State::SendAck;
+        event
+    }
+
+    /// Aborts the ramp-up of the transmitter, the receiver being enabled again once disabled
+    fn restart_rx(&mut self) {
+        self.radio
+            .shorts
+            .write(|w| unsafe { w.bits(RX_SHORTS | DISABLED_RXEN) });
+        self.state = State::RestartRx;
+        self.radio.tasks_disable.write(|w| unsafe { w.bits(1) });
+    }
+
+    fn crc_ok(&self) -> bool {
+        self.radio.crcstatus.read().bits() & 1 == 1
+    }
+
+    fn rssi(&self) -> i8 {
+        -((self.radio.rssisample.read().bits() & 0x7f) as i8)
+    }
+
+    /// Moves the radio to the DISABLED state, without waiting for the interrupt handler
+    fn disable(&mut self) {
+        self.radio.shorts.reset();
+        self.radio.tasks_disable.write(|w| unsafe { w.bits(1) });
+        // STATE is 0 in the DISABLED state
+        while self.radio.state.read().bits() != 0 {}
+        compiler_fence(Ordering::Acquire);
+    }
+}
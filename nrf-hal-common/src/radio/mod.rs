@@ -0,0 +1,10 @@
+//! HAL interface to the RADIO peripheral.
+//!
+//! The RADIO is configured for one protocol at a time, so every driver in this module takes
+//! ownership of the peripheral:
+//!
+//! - [`ble`]: Bluetooth Low Energy link layer PDUs, including advertising
+//! - [`esb`]: Enhanced ShockBurst, compatible with the nRF24L01+
+
+pub mod ble;
+pub mod esb;
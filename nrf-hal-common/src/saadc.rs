@@ -32,7 +32,12 @@ use crate::pac::{saadc_ns as saadc, SAADC_NS as SAADC};
 #[cfg(not(any(feature = "9160", feature = "5340-app")))]
 use crate::pac::{saadc, SAADC};
 
+#[cfg(any(feature = "9160", feature = "5340-app"))]
+use crate::dppi::{Dppi as PpiChannel, Publish, Subscribe};
+#[cfg(not(any(feature = "9160", feature = "5340-app")))]
+use crate::ppi::ConfigurablePpi as PpiChannel;
 use core::sync::atomic::{compiler_fence, Ordering::SeqCst};
+use embedded_dma::WriteBuffer;
 
 pub use saadc::{
     ch::config::{GAIN_A as Gain, REFSEL_A as Reference, RESP_A as Resistor, TACQ_A as Time},
@@ -48,15 +53,20 @@ pub trait Channel {
     fn channel() -> u8;
 }
 
-// Only 1 channel is allowed right now, a discussion needs to be had as to how
-// multiple channels should work (See "scan mode" in the datasheet).
-// Issue: https://github.com/nrf-rs/nrf-hal/issues/82
-
 /// Interface for the SAADC peripheral.
 ///
 /// External analog channels supported by the SAADC implement the `Channel` trait.
-/// Currently, use of only one channel is allowed.
-pub struct Saadc(SAADC);
+/// [`read_channel`](Saadc::read_channel) samples a single channel, [`scan`](Saadc::scan) samples
+/// up to 8 channels at once and [`continuous`](Saadc::continuous) samples them repeatedly into
+/// double-buffered memory.
+pub struct Saadc {
+    saadc: SAADC,
+    /// `CH[0].CONFIG` value used by `read_channel`.
+    config: u32,
+    resolution: Resolution,
+    gain: Gain,
+    reference: Reference,
+}
 
 impl Saadc {
     pub fn new(saadc: SAADC, config: SaadcConfig) -> Self {
@@ -89,19 +99,36 @@ impl Saadc {
             w
         });
         saadc.ch[0].pseln.write(|w| w.pseln().nc());
+        let config = saadc.ch[0].config.read().bits();
 
-        // Calibrate
-        saadc.events_calibratedone.reset();
-        saadc.tasks_calibrateoffset.write(|w| unsafe { w.bits(1) });
-        while saadc.events_calibratedone.read().bits() == 0 {}
+        let mut saadc = Saadc {
+            saadc,
+            config,
+            resolution,
+            gain,
+            reference,
+        };
+        saadc.calibrate();
+        saadc
+    }
 
-        Saadc(saadc)
+    /// Calibrates the offset of the SAADC. Note that this is a blocking operation.
+    ///
+    /// The SAADC is calibrated when created; calibrating again is recommended when the
+    /// temperature changes by more than 10 °C.
+    pub fn calibrate(&mut self) {
+        self.saadc.events_calibratedone.reset();
+        self.saadc
+            .tasks_calibrateoffset
+            .write(|w| unsafe { w.bits(1) });
+        while self.saadc.events_calibratedone.read().bits() == 0 {}
+        self.saadc.events_calibratedone.reset();
     }
 
     /// Disable SAADC and return the low-level peripheral handle
     pub fn free(self) -> SAADC {
-        self.0.enable.write(|w| w.enable().disabled());
-        self.0
+        self.saadc.enable.write(|w| w.enable().disabled());
+        self.saadc
     }
 
     /// Sample channel `PIN` for the configured ADC acquisition time in differential input mode.
@@ -109,29 +136,29 @@ impl Saadc {
     #[allow(clippy::result_unit_err)]
     pub fn read_channel<PIN: Channel>(&mut self, _pin: &mut PIN) -> Result<i16, ()> {
         match PIN::channel() {
-            0 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input0()),
-            1 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input1()),
-            2 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input2()),
-            3 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input3()),
-            4 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input4()),
-            5 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input5()),
-            6 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input6()),
-            7 => self.0.ch[0].pselp.write(|w| w.pselp().analog_input7()),
+            0 => self.saadc.ch[0].pselp.write(|w| w.pselp().analog_input0()),
+            1 => self.saadc.ch[0].pselp.write(|w| w.pselp().analog_input1()),
+            2 => self.saadc.ch[0].pselp.write(|w| w.pselp().analog_input2()),
+            3 => self.saadc.ch[0].pselp.write(|w| w.pselp().analog_input3()),
+            4 => self.saadc.ch[0].pselp.write(|w| w.pselp().analog_input4()),
+            5 => self.saadc.ch[0].pselp.write(|w| w.pselp().analog_input5()),
+            6 => self.saadc.ch[0].pselp.write(|w| w.pselp().analog_input6()),
+            7 => self.saadc.ch[0].pselp.write(|w| w.pselp().analog_input7()),
             #[cfg(not(feature = "9160"))]
-            8 => self.0.ch[0].pselp.write(|w| w.pselp().vdd()),
+            8 => self.saadc.ch[0].pselp.write(|w| w.pselp().vdd()),
             #[cfg(any(feature = "52833", feature = "52840"))]
-            13 => self.0.ch[0].pselp.write(|w| w.pselp().vddhdiv5()),
+            13 => self.saadc.ch[0].pselp.write(|w| w.pselp().vddhdiv5()),
             // This can never happen with the `Channel` implementations provided, as the only analog
             // pins have already been covered.
             _ => return Err(()),
         }
 
         let mut val: i16 = 0;
-        self.0
+        self.saadc
             .result
             .ptr
             .write(|w| unsafe { w.ptr().bits(((&mut val) as *mut _) as u32) });
-        self.0
+        self.saadc
             .result
             .maxcnt
             .write(|w| unsafe { w.maxcnt().bits(1) });
@@ -140,14 +167,14 @@ impl Saadc {
         // pointer and maxcount have been set.
         compiler_fence(SeqCst);
 
-        self.0.tasks_start.write(|w| unsafe { w.bits(1) });
-        self.0.tasks_sample.write(|w| unsafe { w.bits(1) });
+        self.saadc.tasks_start.write(|w| unsafe { w.bits(1) });
+        self.saadc.tasks_sample.write(|w| unsafe { w.bits(1) });
 
-        while self.0.events_end.read().bits() == 0 {}
-        self.0.events_end.reset();
+        while self.saadc.events_end.read().bits() == 0 {}
+        self.saadc.events_end.reset();
 
         // Will only occur if more than one channel has been enabled.
-        if self.0.result.amount.read().bits() != 1 {
+        if self.saadc.result.amount.read().bits() != 1 {
             return Err(());
         }
 
@@ -156,6 +183,287 @@ impl Saadc {
 
         Ok(val)
     }
+
+    /// Samples all `channels` at once, in scan mode. Note that this is a blocking operation.
+    ///
+    /// Each channel is converted with its own configuration, the results are returned in the
+    /// order of `channels`.
+    pub fn scan<const N: usize>(&mut self, channels: &[ScanChannel; N]) -> Result<[i16; N], Error> {
+        self.configure_channels(channels)?;
+
+        let mut results = [0; N];
+        self.saadc
+            .result
+            .ptr
+            .write(|w| unsafe { w.ptr().bits(results.as_mut_ptr() as u32) });
+        self.saadc
+            .result
+            .maxcnt
+            .write(|w| unsafe { w.maxcnt().bits(N as _) });
+
+        // Conservative compiler fence to prevent starting the ADC before the
+        // pointer and maxcount have been set.
+        compiler_fence(SeqCst);
+
+        self.saadc.tasks_start.write(|w| unsafe { w.bits(1) });
+        self.saadc.tasks_sample.write(|w| unsafe { w.bits(1) });
+
+        while self.saadc.events_end.read().bits() == 0 {}
+        self.saadc.events_end.reset();
+
+        // Second fence to prevent optimizations creating issues with the EasyDMA-modified `results`.
+        compiler_fence(SeqCst);
+
+        self.restore_single_channel();
+        Ok(results)
+    }
+
+    /// Starts sampling `channels` continuously into the two `buffers`, alternately.
+    ///
+    /// Every sample of the channels appends one result per channel, in the order of `channels`,
+    /// to the buffer being filled. The buffers must have the same length, which must be a
+    /// multiple of the number of channels. The EasyDMA transfer is restarted on the other buffer
+    /// by `ppi`, which connects the `END` event to the `START` task, so no sample is missed when
+    /// switching buffers. On the nRF9160 and nRF5340, `ppi` is a DPPI channel.
+    ///
+    /// Sampling is paced as selected by `pacing`: the internal timer only supports a single
+    /// channel, with [`Pacing::Task`] the `SAMPLE` task can be triggered by a [`Timer`] through
+    /// another PPI channel:
+    ///
+    /// ```ignore
+    /// let mut timer = Timer::periodic(board.TIMER1);
+    /// ppi.ppi1.set_event_endpoint(timer.event_compare_cc0());
+    /// ppi.ppi1.set_task_endpoint(sampling.task_sample());
+    /// ppi.ppi1.enable();
+    /// timer.start(1_000u32); // 1 kHz
+    /// ```
+    ///
+    /// or, with DPPI:
+    ///
+    /// ```ignore
+    /// dppi.dppi1.publish(timer.event_compare_cc0());
+    /// dppi.dppi1.subscribe(sampling.task_sample());
+    /// dppi.dppi1.enable();
+    /// ```
+    ///
+    /// On error, the SAADC, the PPI channel and the buffers are returned along with the error.
+    ///
+    /// [`Timer`]: crate::timer::Timer
+    pub fn continuous<B, P>(
+        mut self,
+        channels: &[ScanChannel],
+        pacing: Pacing,
+        mut ppi: P,
+        mut buffers: [B; 2],
+    ) -> Result<Continuous<B, P>, (Error, Saadc, P, [B; 2])>
+    where
+        B: WriteBuffer<Word = i16> + 'static,
+        P: PpiChannel,
+    {
+        let (ptr0, len) = unsafe { buffers[0].write_buffer() };
+        let (ptr1, len1) = unsafe { buffers[1].write_buffer() };
+        if len == 0 || len != len1 || len % channels.len().max(1) != 0 || len > MAX_RESULTS {
+            return Err((Error::BufferLength, self, ppi, buffers));
+        }
+        match pacing {
+            Pacing::Internal(_) if channels.len() != 1 => {
+                return Err((Error::InternalPacingWithScan, self, ppi, buffers))
+            }
+            Pacing::Internal(cc) if !(80..=2047).contains(&cc) => {
+                return Err((Error::InvalidSampleRate, self, ppi, buffers))
+            }
+            _ => {}
+        }
+        if let Err(error) = self.configure_channels(channels) {
+            return Err((error, self, ppi, buffers));
+        }
+
+        #[cfg(not(any(feature = "9160", feature = "5340-app")))]
+        {
+            ppi.set_event_endpoint(&self.saadc.events_end);
+            ppi.set_task_endpoint(&self.saadc.tasks_start);
+        }
+        #[cfg(any(feature = "9160", feature = "5340-app"))]
+        {
+            ppi.publish(&self.saadc.events_end);
+            ppi.subscribe(&self.saadc.tasks_start);
+        }
+        ppi.enable();
+
+        self.saadc
+            .result
+            .ptr
+            .write(|w| unsafe { w.ptr().bits(ptr0 as u32) });
+        self.saadc
+            .result
+            .maxcnt
+            .write(|w| unsafe { w.maxcnt().bits(len as _) });
+        self.saadc.events_started.reset();
+        self.saadc.events_end.reset();
+        compiler_fence(SeqCst);
+
+        self.saadc.tasks_start.write(|w| unsafe { w.bits(1) });
+        while self.saadc.events_started.read().bits() == 0 {}
+        self.saadc.events_started.reset();
+
+        // The pointer is double buffered: the next transfer, started by PPI, fills the other
+        // buffer.
+        self.saadc
+            .result
+            .ptr
+            .write(|w| unsafe { w.ptr().bits(ptr1 as u32) });
+
+        if let Pacing::Internal(cc) = pacing {
+            self.saadc
+                .samplerate
+                .write(|w| unsafe { w.cc().bits(cc) }.mode().timers());
+            // The internal timer is started by the first SAMPLE task.
+            self.saadc.tasks_sample.write(|w| unsafe { w.bits(1) });
+        }
+
+        Ok(Continuous {
+            inner: Some(ContinuousInner {
+                saadc: self,
+                ppi,
+                buffers,
+                filling: 0,
+            }),
+        })
+    }
+
+    /// Sets the limits of channel `channel` and clears its limit events.
+    ///
+    /// The `LIMITH` event is generated when a result of the channel is above `high` and the
+    /// `LIMITL` event when it is below `low`. `channel` is the index of the channel in scan mode,
+    /// and 0 for [`read_channel`](Self::read_channel).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channel` is greater than 7.
+    pub fn set_limits(&mut self, channel: usize, low: i16, high: i16) {
+        self.saadc.ch[channel]
+            .limit
+            .write(|w| unsafe { w.bits(((high as u16 as u32) << 16) | low as u16 as u32) });
+        self.reset_limit_event(channel, Limit::High);
+        self.reset_limit_event(channel, Limit::Low);
+    }
+
+    /// Enables the interrupt for a limit event of channel `channel`.
+    pub fn enable_limit_interrupt(&mut self, channel: usize, limit: Limit) {
+        self.saadc
+            .intenset
+            .write(|w| unsafe { w.bits(limit.inten_mask(channel)) });
+    }
+
+    /// Disables the interrupt for a limit event of channel `channel`.
+    pub fn disable_limit_interrupt(&mut self, channel: usize, limit: Limit) {
+        self.saadc
+            .intenclr
+            .write(|w| unsafe { w.bits(limit.inten_mask(channel)) });
+    }
+
+    /// Checks if a limit event of channel `channel` has been triggered.
+    pub fn is_limit_event_triggered(&self, channel: usize, limit: Limit) -> bool {
+        match limit {
+            Limit::High => self.saadc.events_ch[channel].limith.read().bits() != 0,
+            Limit::Low => self.saadc.events_ch[channel].limitl.read().bits() != 0,
+        }
+    }
+
+    /// Resets a limit event of channel `channel`.
+    pub fn reset_limit_event(&self, channel: usize, limit: Limit) {
+        match limit {
+            Limit::High => self.saadc.events_ch[channel].limith.reset(),
+            Limit::Low => self.saadc.events_ch[channel].limitl.reset(),
+        }
+    }
+
+    /// Returns reference to the `LIMITH` event endpoint of channel `channel`, for PPI.
+    pub fn event_limit_high(&self, channel: usize) -> &saadc::events_ch::LIMITH {
+        &self.saadc.events_ch[channel].limith
+    }
+
+    /// Returns reference to the `LIMITL` event endpoint of channel `channel`, for PPI.
+    pub fn event_limit_low(&self, channel: usize) -> &saadc::events_ch::LIMITL {
+        &self.saadc.events_ch[channel].limitl
+    }
+
+    /// Returns the conversion to millivolts of the results of
+    /// [`read_channel`](Self::read_channel).
+    ///
+    /// `vdd_mv` is the supply voltage in millivolts. It is only used when the reference is
+    /// `Reference::VDD1_4`.
+    pub fn conversion(&self, vdd_mv: u32) -> Conversion {
+        Conversion::new(self.resolution, self.gain, self.reference, false, vdd_mv)
+    }
+
+    /// Returns the conversion to millivolts of the results of `channel` in scan and continuous
+    /// modes.
+    ///
+    /// `vdd_mv` is the supply voltage in millivolts. It is only used when the reference of the
+    /// channel is `Reference::VDD1_4`.
+    pub fn channel_conversion(&self, channel: &ScanChannel, vdd_mv: u32) -> Conversion {
+        Conversion::new(
+            self.resolution,
+            channel.gain,
+            channel.reference,
+            channel.negative.is_some(),
+            vdd_mv,
+        )
+    }
+
+    /// Configures the channels used in scan mode and disables the other ones.
+    fn configure_channels(&mut self, channels: &[ScanChannel]) -> Result<(), Error> {
+        if channels.is_empty() || channels.len() > 8 {
+            return Err(Error::ChannelCount);
+        }
+        let mut psels = [(0, 0); 8];
+        for (psel, channel) in psels.iter_mut().zip(channels) {
+            let p = psel_bits(channel.positive).ok_or(Error::InvalidInput)?;
+            let n = match channel.negative {
+                Some(negative) => psel_bits(negative).ok_or(Error::InvalidInput)?,
+                None => 0,
+            };
+            *psel = (p, n);
+        }
+
+        for (i, ch) in self.saadc.ch.iter().enumerate() {
+            match channels.get(i) {
+                Some(channel) => {
+                    ch.config.write(|w| {
+                        w.refsel().variant(channel.reference);
+                        w.gain().variant(channel.gain);
+                        w.tacq().variant(channel.time);
+                        if channel.negative.is_some() {
+                            w.mode().diff();
+                        } else {
+                            w.mode().se();
+                        }
+                        w.resp().variant(channel.resistor);
+                        w.resn().bypass();
+                        w.burst().enabled();
+                        w
+                    });
+                    ch.pselp.write(|w| unsafe { w.bits(psels[i].0) });
+                    ch.pseln.write(|w| unsafe { w.bits(psels[i].1) });
+                }
+                None => ch.pselp.write(|w| w.pselp().nc()),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restores the single channel configuration used by `read_channel`.
+    fn restore_single_channel(&mut self) {
+        self.saadc.ch[0]
+            .config
+            .write(|w| unsafe { w.bits(self.config) });
+        self.saadc.ch[0].pseln.write(|w| w.pseln().nc());
+        for ch in &self.saadc.ch[1..] {
+            ch.pselp.write(|w| w.pselp().nc());
+        }
+    }
 }
 
 /// Used to configure the SAADC peripheral.
@@ -222,6 +530,308 @@ impl Default for SaadcConfig {
     }
 }
 
+/// Configuration of a channel sampled by [`Saadc::scan`] and [`Saadc::continuous`].
+///
+/// The gain, reference, acquisition time and resistor default to the values of
+/// `SaadcConfig::default()`.
+#[derive(Clone, Copy)]
+pub struct ScanChannel {
+    positive: u8,
+    negative: Option<u8>,
+    gain: Gain,
+    reference: Reference,
+    time: Time,
+    resistor: Resistor,
+}
+
+impl ScanChannel {
+    /// Samples the voltage of `pin`.
+    pub fn single_ended<PIN: Channel>(_pin: &mut PIN) -> Self {
+        Self::new(PIN::channel(), None)
+    }
+
+    /// Samples the voltage between `positive` and `negative`.
+    pub fn differential<P: Channel, N: Channel>(_positive: &mut P, _negative: &mut N) -> Self {
+        Self::new(P::channel(), Some(N::channel()))
+    }
+
+    fn new(positive: u8, negative: Option<u8>) -> Self {
+        let config = SaadcConfig::default();
+        Self {
+            positive,
+            negative,
+            gain: config.gain,
+            reference: config.reference,
+            time: config.time,
+            resistor: config.resistor,
+        }
+    }
+
+    /// Sets the gain used to control the effective input range of the channel.
+    pub fn gain(mut self, gain: Gain) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Sets the reference voltage of the channel.
+    pub fn reference(mut self, reference: Reference) -> Self {
+        self.reference = reference;
+        self
+    }
+
+    /// Sets the acquisition time of the channel.
+    pub fn time(mut self, time: Time) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Sets the resistor ladder of the positive input.
+    pub fn resistor(mut self, resistor: Resistor) -> Self {
+        self.resistor = resistor;
+        self
+    }
+}
+
+/// Limits of a channel, see [`Saadc::set_limits`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Limit {
+    /// A result was above the high limit.
+    High,
+    /// A result was below the low limit.
+    Low,
+}
+
+impl Limit {
+    /// Returns the `INTEN` bit of the event of `channel`.
+    fn inten_mask(self, channel: usize) -> u32 {
+        assert!(channel < 8);
+        match self {
+            Limit::High => 1 << (6 + 2 * channel),
+            Limit::Low => 1 << (7 + 2 * channel),
+        }
+    }
+}
+
+/// Conversion of raw results to millivolts.
+///
+/// The voltage of an input is `result * reference / (gain * 2^(resolution - m))`, with `m` being
+/// 1 for differential channels and 0 otherwise.
+#[derive(Clone, Copy, Debug)]
+pub struct Conversion {
+    /// Numerator and denominator of the gain.
+    gain: (i32, i32),
+    reference_mv: i32,
+    /// Number of bits of the magnitude of results.
+    bits: u32,
+}
+
+impl Conversion {
+    fn new(
+        resolution: Resolution,
+        gain: Gain,
+        reference: Reference,
+        differential: bool,
+        vdd_mv: u32,
+    ) -> Self {
+        let bits = match resolution {
+            Resolution::_8BIT => 8,
+            Resolution::_10BIT => 10,
+            Resolution::_12BIT => 12,
+            Resolution::_14BIT => 14,
+        };
+        let gain = match gain {
+            Gain::GAIN1_6 => (1, 6),
+            Gain::GAIN1_5 => (1, 5),
+            Gain::GAIN1_4 => (1, 4),
+            Gain::GAIN1_3 => (1, 3),
+            Gain::GAIN1_2 => (1, 2),
+            Gain::GAIN1 => (1, 1),
+            Gain::GAIN2 => (2, 1),
+            Gain::GAIN4 => (4, 1),
+        };
+        let reference_mv = match reference {
+            Reference::INTERNAL => 600,
+            Reference::VDD1_4 => vdd_mv as i32 / 4,
+        };
+
+        Self {
+            gain,
+            reference_mv,
+            bits: bits - differential as u32,
+        }
+    }
+
+    /// Converts a raw result to millivolts.
+    pub fn millivolts(&self, raw: i16) -> i32 {
+        let (num, den) = self.gain;
+        (raw as i32 * self.reference_mv * den / num) >> self.bits
+    }
+}
+
+/// Returns the `PSELP`/`PSELN` value selecting the input with `Channel` ID `channel`.
+fn psel_bits(channel: u8) -> Option<u32> {
+    match channel {
+        // AnalogInput0 to AnalogInput7.
+        0..=7 => Some(channel as u32 + 1),
+        // VDD.
+        #[cfg(not(feature = "9160"))]
+        8 => Some(9),
+        // VDDHDIV5.
+        #[cfg(any(feature = "52833", feature = "52840"))]
+        13 => Some(13),
+        _ => None,
+    }
+}
+
+/// Maximum number of results of a single EasyDMA transfer.
+const MAX_RESULTS: usize = (1 << 15) - 1;
+
+/// What triggers the samples in continuous mode.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Pacing {
+    /// The internal timer, sampling every `n` cycles of the 16 MHz clock, with `n` in
+    /// `80..=2047`. Only supported with a single channel.
+    Internal(u16),
+    /// The `SAMPLE` task, triggered by software or through PPI, see
+    /// [`Continuous::task_sample`].
+    Task,
+}
+
+/// Errors of the scan and continuous modes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// No channel, or more than 8 channels, were given.
+    ChannelCount,
+    /// A channel does not map to an input of the SAADC.
+    InvalidInput,
+    /// The buffers are empty, of different lengths, too long, or their length is not a multiple
+    /// of the number of channels.
+    BufferLength,
+    /// The internal timer was selected to sample more than one channel.
+    InternalPacingWithScan,
+    /// The internal timer period is out of range.
+    InvalidSampleRate,
+}
+
+/// Continuous sampling, started by [`Saadc::continuous`].
+///
+/// Dropping it stops the sampling.
+pub struct Continuous<B, P: PpiChannel> {
+    // FIXME: Always `Some`, only using `Option` here to allow moving fields out of `inner`.
+    inner: Option<ContinuousInner<B, P>>,
+}
+
+struct ContinuousInner<B, P: PpiChannel> {
+    saadc: Saadc,
+    ppi: P,
+    buffers: [B; 2],
+    /// Index of the buffer being filled.
+    filling: usize,
+}
+
+impl<B, P> Continuous<B, P>
+where
+    B: WriteBuffer<Word = i16> + 'static,
+    P: PpiChannel,
+{
+    /// Returns `true` if a buffer is full, so [`next`](Self::next) will not block.
+    pub fn is_ready(&self) -> bool {
+        self.inner().saadc.saadc.events_end.read().bits() != 0
+    }
+
+    /// Waits for the buffer being filled to be full and passes its content to `f`.
+    ///
+    /// The other buffer is filled meanwhile. `f` must return before it is full, otherwise the
+    /// samples it contains are overwritten by the following ones.
+    pub fn next<R>(&mut self, f: impl FnOnce(&[i16]) -> R) -> R {
+        let inner = self
+            .inner
+            .as_mut()
+            .unwrap_or_else(|| unsafe { core::hint::unreachable_unchecked() });
+        let saadc = &inner.saadc.saadc;
+
+        while saadc.events_end.read().bits() == 0 {}
+        saadc.events_end.reset();
+        // Once PPI has restarted the transfer on the other buffer, the full one is no longer
+        // written by EasyDMA.
+        while saadc.events_started.read().bits() == 0 {}
+        saadc.events_started.reset();
+        compiler_fence(SeqCst);
+
+        let (ptr, len) = unsafe { inner.buffers[inner.filling].write_buffer() };
+        let result = f(unsafe { core::slice::from_raw_parts(ptr, len) });
+        compiler_fence(SeqCst);
+
+        // Use the buffer again for the transfer after the current one.
+        saadc
+            .result
+            .ptr
+            .write(|w| unsafe { w.ptr().bits(ptr as u32) });
+        inner.filling ^= 1;
+
+        result
+    }
+
+    /// Returns a reference to the `SAMPLE` task, to trigger it through PPI with
+    /// [`Pacing::Task`].
+    pub fn task_sample(&self) -> &saadc::TASKS_SAMPLE {
+        &self.inner().saadc.saadc.tasks_sample
+    }
+
+    /// Returns the SAADC, to access its limit events while sampling.
+    pub fn saadc(&self) -> &Saadc {
+        &self.inner().saadc
+    }
+
+    /// Stops sampling and returns the SAADC, the PPI channel and the buffers.
+    pub fn stop(mut self) -> (Saadc, P, [B; 2]) {
+        let mut inner = self
+            .inner
+            .take()
+            .unwrap_or_else(|| unsafe { core::hint::unreachable_unchecked() });
+        inner.stop();
+        (inner.saadc, inner.ppi, inner.buffers)
+    }
+
+    fn inner(&self) -> &ContinuousInner<B, P> {
+        self.inner
+            .as_ref()
+            .unwrap_or_else(|| unsafe { core::hint::unreachable_unchecked() })
+    }
+}
+
+impl<B, P: PpiChannel> ContinuousInner<B, P> {
+    fn stop(&mut self) {
+        let saadc = &self.saadc.saadc;
+        self.ppi.disable();
+        #[cfg(any(feature = "9160", feature = "5340-app"))]
+        {
+            saadc.events_end.unpublish();
+            saadc.tasks_start.unsubscribe();
+        }
+        saadc.tasks_stop.write(|w| unsafe { w.bits(1) });
+        while saadc.events_stopped.read().bits() == 0 {}
+        saadc.events_stopped.reset();
+        saadc.events_started.reset();
+        saadc.events_end.reset();
+        compiler_fence(SeqCst);
+
+        saadc.samplerate.write(|w| w.mode().task());
+        self.saadc.restore_single_channel();
+    }
+}
+
+impl<B, P: PpiChannel> Drop for Continuous<B, P> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.stop();
+        }
+    }
+}
+
 #[cfg(feature = "embedded-hal-02")]
 impl<PIN> embedded_hal_02::adc::OneShot<Saadc, i16, PIN> for Saadc
 where
@@ -34,6 +34,12 @@ use crate::gpio::{Floating, Input, Output, Pin, PushPull};
 use crate::target_constants::{EASY_DMA_SIZE, FORCE_COPY_BUFFER_SIZE};
 use crate::{slice_in_ram, slice_in_ram_or, DmaSlice};
 
+#[cfg(feature = "embedded-hal-async")]
+use {
+    crate::asynch::{AtomicWaker, OnDrop},
+    core::{future::poll_fn, task::Poll},
+};
+
 /// Interface to a SPIM instance.
 ///
 /// This is a very basic interface that comes with the following limitations:
@@ -225,6 +231,19 @@ where
 
     /// Internal helper function to setup and execute SPIM DMA transfer.
     fn do_spi_dma_transfer(&mut self, tx: DmaSlice, rx: DmaSlice) -> Result<(), Error> {
+        self.start_spi_dma_transfer(&tx, &rx);
+
+        // Wait for END event.
+        //
+        // This event is triggered once both transmitting and receiving are
+        // done.
+        while self.0.events_end.read().bits() == 0 {}
+
+        self.finish_spi_dma_transfer(&tx, &rx)
+    }
+
+    /// Internal helper function to setup and start a SPIM DMA transfer.
+    fn start_spi_dma_transfer(&mut self, tx: &DmaSlice, rx: &DmaSlice) {
         // Conservative compiler fence to prevent optimizations that do not
         // take in to account actions by DMA. The fence has been placed here,
         // before any DMA action has started.
@@ -259,13 +278,10 @@ where
         // take in to account actions by DMA. The fence has been placed here,
         // after all possible DMA actions have completed.
         compiler_fence(SeqCst);
+    }
 
-        // Wait for END event.
-        //
-        // This event is triggered once both transmitting and receiving are
-        // done.
-        while self.0.events_end.read().bits() == 0 {}
-
+    /// Internal helper function to complete a SPIM DMA transfer once the END event occurred.
+    fn finish_spi_dma_transfer(&mut self, tx: &DmaSlice, rx: &DmaSlice) -> Result<(), Error> {
         // Reset the event, otherwise it will always read `1` from now on.
         self.0.events_end.write(|w| w);
 
@@ -469,6 +485,162 @@ where
     }
 }
 
+#[cfg(feature = "embedded-hal-async")]
+impl<T> Spim<T>
+where
+    T: Instance,
+{
+    /// Handles the SPIM interrupt for async operations.
+    ///
+    /// This must be called from the interrupt handler of the SPIM instance when using the
+    /// `embedded-hal-async` implementation, and the interrupt must be unmasked in the NVIC.
+    pub fn on_interrupt() {
+        T::regs().intenclr.write(|w| w.end().set_bit());
+        T::waker().wake();
+    }
+
+    /// Async version of `do_spi_dma_transfer`, waiting for the END event through the interrupt.
+    ///
+    /// Dropping the returned future stops the transfer.
+    async fn do_spi_dma_transfer_async(&mut self, tx: DmaSlice, rx: DmaSlice) -> Result<(), Error> {
+        let guard = OnDrop::new(|| {
+            let regs = T::regs();
+            regs.intenclr.write(|w| w.end().set_bit());
+            // Once the transfer has ended, the peripheral is idle and STOPPED is not generated.
+            // The transfer can also end right before it is stopped, so END ends the wait as well.
+            if regs.events_end.read().bits() == 0 {
+                regs.events_stopped.reset();
+                regs.tasks_stop.write(|w| unsafe { w.bits(1) });
+                while regs.events_stopped.read().bits() == 0 && regs.events_end.read().bits() == 0 {
+                }
+            }
+            regs.events_stopped.reset();
+            regs.events_end.reset();
+        });
+
+        self.start_spi_dma_transfer(&tx, &rx);
+
+        poll_fn(|cx| {
+            T::waker().register(cx.waker());
+            if self.0.events_end.read().bits() != 0 {
+                return Poll::Ready(());
+            }
+            self.0.intenset.write(|w| w.end().set_bit());
+            Poll::Pending
+        })
+        .await;
+        guard.defuse();
+
+        self.finish_spi_dma_transfer(&tx, &rx)
+    }
+
+    async fn transfer_split_uneven_async(
+        &mut self,
+        tx_buffer: &[u8],
+        rx_buffer: &mut [u8],
+    ) -> Result<(), Error> {
+        // NOTE: RAM slice check for `rx_buffer` is not necessary, as a mutable
+        // slice can only be built from data located in RAM.
+        if slice_in_ram(tx_buffer) {
+            let txi = tx_buffer
+                .chunks(EASY_DMA_SIZE)
+                .map(DmaSlice::from_slice)
+                .chain(repeat_with(DmaSlice::null));
+            let rxi = rx_buffer
+                .chunks_mut(EASY_DMA_SIZE)
+                .map(|chunk| DmaSlice::from_slice(chunk))
+                .chain(repeat_with(DmaSlice::null));
+
+            for (t, r) in txi.zip(rxi).take_while(|(t, r)| t.ptr != 0 || r.ptr != 0) {
+                self.do_spi_dma_transfer_async(t, r).await?;
+            }
+        } else {
+            let mut buf = [0u8; FORCE_COPY_BUFFER_SIZE];
+            let txi = tx_buffer
+                .chunks(FORCE_COPY_BUFFER_SIZE)
+                .map(Some)
+                .chain(repeat_with(|| None));
+            let rxi = rx_buffer
+                .chunks_mut(FORCE_COPY_BUFFER_SIZE)
+                .map(|chunk| DmaSlice::from_slice(chunk))
+                .chain(repeat_with(DmaSlice::null));
+            for (tx_chunk, r) in txi.zip(rxi).take_while(|(t, r)| t.is_some() || r.ptr != 0) {
+                let t = if let Some(tx_chunk) = tx_chunk {
+                    buf[..tx_chunk.len()].copy_from_slice(tx_chunk);
+                    DmaSlice::from_slice(&buf[..tx_chunk.len()])
+                } else {
+                    DmaSlice::null()
+                };
+                self.do_spi_dma_transfer_async(t, r).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Async implementation, driven by the SPIM interrupt.
+///
+/// See [`Spim::on_interrupt`] for the required interrupt handler. Dropping the returned future
+/// stops the ongoing transfer.
+#[cfg(feature = "embedded-hal-async")]
+impl<T: Instance> embedded_hal_async::spi::SpiBus for Spim<T> {
+    async fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        // A mutable slice can only be built from data in RAM.
+        assert!(slice_in_ram(words));
+
+        for chunk in words.chunks(EASY_DMA_SIZE) {
+            self.do_spi_dma_transfer_async(DmaSlice::null(), DmaSlice::from_slice(chunk))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        if slice_in_ram(words) {
+            for chunk in words.chunks(EASY_DMA_SIZE) {
+                self.do_spi_dma_transfer_async(DmaSlice::from_slice(chunk), DmaSlice::null())
+                    .await?;
+            }
+        } else {
+            let mut buf = [0u8; FORCE_COPY_BUFFER_SIZE];
+            for chunk in words.chunks(FORCE_COPY_BUFFER_SIZE) {
+                buf[..chunk.len()].copy_from_slice(chunk);
+                self.do_spi_dma_transfer_async(
+                    DmaSlice::from_slice(&buf[..chunk.len()]),
+                    DmaSlice::null(),
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        self.transfer_split_uneven_async(write, read).await
+    }
+
+    async fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        // A mutable slice can only be built from data in RAM.
+        assert!(slice_in_ram(words));
+
+        for chunk in words.chunks(EASY_DMA_SIZE) {
+            self.do_spi_dma_transfer_async(
+                DmaSlice::from_slice(chunk),
+                DmaSlice::from_slice(chunk),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        // This implementation doesn't buffer operations, so there is nothing to flush.
+        Ok(())
+    }
+}
+
 /// GPIO pins for SPIM interface
 pub struct Pins {
     /// SPI clock.
@@ -503,10 +675,34 @@ impl spi::Error for Error {
 pub trait Instance: Deref<Target = spim0::RegisterBlock> + sealed::Sealed {}
 
 mod sealed {
-    pub trait Sealed {}
+    #[cfg(feature = "embedded-hal-async")]
+    use super::{spim0, AtomicWaker};
+
+    pub trait Sealed {
+        #[cfg(feature = "embedded-hal-async")]
+        fn regs() -> &'static spim0::RegisterBlock;
+        #[cfg(feature = "embedded-hal-async")]
+        fn waker() -> &'static AtomicWaker;
+    }
+}
+
+macro_rules! impl_sealed {
+    ($spim:ident) => {
+        impl sealed::Sealed for $spim {
+            #[cfg(feature = "embedded-hal-async")]
+            fn regs() -> &'static spim0::RegisterBlock {
+                unsafe { &*$spim::ptr() }
+            }
+            #[cfg(feature = "embedded-hal-async")]
+            fn waker() -> &'static AtomicWaker {
+                static WAKER: AtomicWaker = AtomicWaker::new();
+                &WAKER
+            }
+        }
+    };
 }
 
-impl sealed::Sealed for SPIM0 {}
+impl_sealed!(SPIM0);
 impl Instance for SPIM0 {}
 
 #[cfg(any(
@@ -519,7 +715,7 @@ impl Instance for SPIM0 {}
 mod _spim1 {
     use super::*;
     impl Instance for SPIM1 {}
-    impl sealed::Sealed for SPIM1 {}
+    impl_sealed!(SPIM1);
 }
 
 #[cfg(any(
@@ -531,12 +727,12 @@ mod _spim1 {
 mod _spim2 {
     use super::*;
     impl Instance for SPIM2 {}
-    impl sealed::Sealed for SPIM2 {}
+    impl_sealed!(SPIM2);
 }
 
 #[cfg(any(feature = "52833", feature = "52840", feature = "9160"))]
 mod _spim3 {
     use super::*;
     impl Instance for SPIM3 {}
-    impl sealed::Sealed for SPIM3 {}
+    impl_sealed!(SPIM3);
 }
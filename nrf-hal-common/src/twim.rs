@@ -26,6 +26,12 @@ use crate::{
     target_constants::{EASY_DMA_SIZE, FORCE_COPY_BUFFER_SIZE},
 };
 
+#[cfg(feature = "embedded-hal-async")]
+use {
+    crate::asynch::{AtomicWaker, OnDrop},
+    core::{future::poll_fn, task::Poll},
+};
+
 pub use twim0::frequency::FREQUENCY_A as Frequency;
 
 /// Interface to a TWIM instance.
@@ -447,6 +453,136 @@ where
     }
 }
 
+#[cfg(feature = "embedded-hal-async")]
+impl<T> Twim<T>
+where
+    T: Instance,
+{
+    /// Handles the TWIM interrupt for async operations.
+    ///
+    /// This must be called from the interrupt handler of the TWIM instance (for example
+    /// `SPIM0_SPIS0_TWIM0_TWIS0_SPI0_TWI0` for TWIM0) when using the `embedded-hal-async`
+    /// implementation, and the interrupt must be unmasked in the NVIC.
+    pub fn on_interrupt() {
+        let regs = T::regs();
+        regs.intenclr.write(|w| {
+            w.stopped().set_bit();
+            w.error().set_bit();
+            w.suspended().set_bit();
+            w
+        });
+        T::waker().wake();
+    }
+
+    /// Wait for stop or error, without blocking.
+    async fn wait_async(&mut self) {
+        poll_fn(|cx| {
+            T::waker().register(cx.waker());
+
+            if self.0.events_error.read().bits() != 0 {
+                self.0.events_error.reset();
+                self.0.tasks_stop.write(|w| unsafe { w.bits(1) });
+            }
+            if self.0.events_stopped.read().bits() != 0 {
+                self.0.events_stopped.reset();
+                return Poll::Ready(());
+            }
+            if self.0.events_suspended.read().bits() != 0 {
+                self.0.events_suspended.reset();
+                return Poll::Ready(());
+            }
+
+            self.0.intenset.write(|w| {
+                w.stopped().set_bit();
+                w.error().set_bit();
+                w.suspended().set_bit();
+                w
+            });
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// Returns a guard that stops the ongoing transfer if the future is dropped before completion.
+    fn stop_on_drop() -> OnDrop<impl FnOnce()> {
+        OnDrop::new(|| {
+            let regs = T::regs();
+            regs.intenclr.write(|w| {
+                w.stopped().set_bit();
+                w.error().set_bit();
+                w.suspended().set_bit();
+                w
+            });
+            // STOPPED is only generated if the transfer is running or suspended. The event stays
+            // set once the transfer is stopped, as it is only reset when the wait completes.
+            if regs.events_stopped.read().bits() == 0 {
+                regs.tasks_stop.write(|w| unsafe { w.bits(1) });
+                regs.tasks_resume.write(|w| unsafe { w.bits(1) });
+                while regs.events_stopped.read().bits() == 0 {}
+            }
+            regs.events_stopped.reset();
+        })
+    }
+
+    async fn write_part_async(
+        &mut self,
+        buffer: &[u8],
+        final_operation: bool,
+    ) -> Result<(), Error> {
+        compiler_fence(SeqCst);
+        unsafe { self.set_tx_buffer(buffer)? };
+
+        // Set appropriate lasttx shortcut.
+        if final_operation {
+            self.0.shorts.write(|w| w.lasttx_stop().enabled());
+        } else {
+            self.0.shorts.write(|w| w.lasttx_suspend().enabled());
+        }
+
+        let guard = Self::stop_on_drop();
+
+        // Start write.
+        self.0.tasks_starttx.write(|w| unsafe { w.bits(1) });
+        self.0.tasks_resume.write(|w| unsafe { w.bits(1) });
+
+        self.wait_async().await;
+        guard.defuse();
+
+        compiler_fence(SeqCst);
+        self.read_errorsrc()?;
+        if self.0.txd.amount.read().bits() != buffer.len() as u32 {
+            return Err(Error::Transmit);
+        }
+
+        Ok(())
+    }
+
+    async fn read_part_async(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
+        compiler_fence(SeqCst);
+        unsafe { self.set_rx_buffer(buffer)? };
+
+        // See `read_part` for why this doesn't suspend.
+        self.0.shorts.write(|w| w.lastrx_stop().enabled());
+
+        let guard = Self::stop_on_drop();
+
+        // Start read.
+        self.0.tasks_startrx.write(|w| unsafe { w.bits(1) });
+        self.0.tasks_resume.write(|w| unsafe { w.bits(1) });
+
+        self.wait_async().await;
+        guard.defuse();
+
+        compiler_fence(SeqCst);
+        self.read_errorsrc()?;
+        if self.0.rxd.amount.read().bits() != buffer.len() as u32 {
+            return Err(Error::Receive);
+        }
+
+        Ok(())
+    }
+}
+
 impl<T> ErrorType for Twim<T> {
     type Error = Error;
 }
@@ -457,6 +593,208 @@ enum OperationType {
     Write,
 }
 
+/// A TWIM transfer of a [`Transaction`].
+#[cfg(feature = "embedded-hal-async")]
+enum Transfer<'a> {
+    /// Writes the buffer, stopping afterwards if it is the final transfer of the transaction.
+    Write(&'a [u8], bool),
+    /// Reads into the buffer.
+    Read(&'a mut [u8]),
+}
+
+/// Combines the operations of an `I2c` transaction into TWIM transfers.
+///
+/// Consecutive operations of the same type are combined into a single transfer, and writes from
+/// flash are copied to RAM, the same way as in the blocking `I2c::transaction`. The async
+/// implementation starts and waits for each transfer in turn.
+#[cfg(feature = "embedded-hal-async")]
+struct Transaction<'a, 'b> {
+    operations: &'a mut [Operation<'b>],
+    /// Index of the current operation.
+    index: usize,
+    /// Offset of the next chunk of the current operation, when it is sent in chunks.
+    chunk_offset: usize,
+    /// Buffer used when writing data from flash, or combining multiple consecutive write operations.
+    tx_copy: [u8; FORCE_COPY_BUFFER_SIZE],
+    /// Number of bytes waiting in `tx_copy` to be sent.
+    pending_tx_bytes: usize,
+    /// Buffer used when combining multiple consecutive read operations.
+    rx_copy: [u8; FORCE_COPY_BUFFER_SIZE],
+    /// Number of bytes from earlier read operations not yet actually read.
+    pending_rx_bytes: usize,
+    /// Index of the last operation of a combined read, whose data is still in `rx_copy`.
+    combined_read: Option<usize>,
+}
+
+#[cfg(feature = "embedded-hal-async")]
+impl<'a, 'b> Transaction<'a, 'b> {
+    fn new(operations: &'a mut [Operation<'b>]) -> Self {
+        Self {
+            operations,
+            index: 0,
+            chunk_offset: 0,
+            tx_copy: [0; FORCE_COPY_BUFFER_SIZE],
+            pending_tx_bytes: 0,
+            rx_copy: [0; FORCE_COPY_BUFFER_SIZE],
+            pending_rx_bytes: 0,
+            combined_read: None,
+        }
+    }
+
+    /// Returns the next transfer, or `None` once all the operations are done.
+    ///
+    /// The previous transfer must have completed successfully.
+    fn next_transfer(&mut self) -> Result<Option<Transfer<'_>>, Error> {
+        self.copy_back_combined_read();
+
+        while self.index < self.operations.len() {
+            let i = self.index;
+            let next_operation_type = match self.operations.get(i + 1) {
+                None => None,
+                Some(Operation::Write(_)) => Some(OperationType::Write),
+                Some(Operation::Read(_)) => Some(OperationType::Read),
+            };
+            let final_operation = next_operation_type.is_none();
+
+            match &self.operations[i] {
+                Operation::Read(buffer) => {
+                    let len = buffer.len();
+                    if len > FORCE_COPY_BUFFER_SIZE - self.pending_rx_bytes {
+                        // Splitting into multiple reads isn't going to work, so just return an
+                        // error.
+                        return Err(Error::RxBufferTooLong);
+                    }
+                    self.index += 1;
+
+                    if self.pending_rx_bytes == 0
+                        && next_operation_type != Some(OperationType::Read)
+                    {
+                        // Simple case: there are no consecutive read operations, so receive
+                        // directly.
+                        return Ok(Some(Transfer::Read(read_buffer(self.operations, i))));
+                    }
+
+                    self.pending_rx_bytes += len;
+
+                    // If the next operation is not a read (or these is no next operation),
+                    // receive into `rx_copy` now.
+                    if next_operation_type != Some(OperationType::Read) {
+                        self.combined_read = Some(i);
+                        return Ok(Some(Transfer::Read(
+                            &mut self.rx_copy[..self.pending_rx_bytes],
+                        )));
+                    }
+                }
+                Operation::Write(buffer) => {
+                    let len = buffer.len();
+                    let in_ram = crate::slice_in_ram(buffer);
+
+                    // Will the current buffer fit in the remaining space in `tx_copy`? If not,
+                    // send `tx_copy` immediately.
+                    if len > FORCE_COPY_BUFFER_SIZE - self.pending_tx_bytes
+                        && self.pending_tx_bytes > 0
+                    {
+                        let pending_tx_bytes = core::mem::replace(&mut self.pending_tx_bytes, 0);
+                        return Ok(Some(Transfer::Write(
+                            &self.tx_copy[..pending_tx_bytes],
+                            false,
+                        )));
+                    }
+
+                    if in_ram
+                        && self.pending_tx_bytes == 0
+                        && next_operation_type != Some(OperationType::Write)
+                    {
+                        // Simple case: the buffer is in RAM, and there are no consecutive write
+                        // operations, so send it directly.
+                        self.index += 1;
+                        return Ok(Some(Transfer::Write(
+                            write_buffer(self.operations, i),
+                            final_operation,
+                        )));
+                    }
+
+                    if len > FORCE_COPY_BUFFER_SIZE {
+                        // This must be true because if it wasn't we must have hit the case above
+                        // to send `tx_copy` immediately and reset `pending_tx_bytes` to 0.
+                        assert!(self.pending_tx_bytes == 0);
+
+                        // Send the buffer in chunks immediately.
+                        let start = self.chunk_offset;
+                        let end = len.min(start + FORCE_COPY_BUFFER_SIZE);
+                        self.tx_copy[..end - start]
+                            .copy_from_slice(&write_buffer(self.operations, i)[start..end]);
+                        if end == len {
+                            self.chunk_offset = 0;
+                            self.index += 1;
+                        } else {
+                            self.chunk_offset = end;
+                        }
+                        return Ok(Some(Transfer::Write(
+                            &self.tx_copy[..end - start],
+                            final_operation && end == len,
+                        )));
+                    }
+
+                    // Copy the current buffer to `tx_copy`. It must fit, as otherwise we would
+                    // have hit one of the cases above.
+                    let pending_tx_bytes = self.pending_tx_bytes;
+                    self.tx_copy[pending_tx_bytes..pending_tx_bytes + len]
+                        .copy_from_slice(write_buffer(self.operations, i));
+                    self.pending_tx_bytes += len;
+                    self.index += 1;
+
+                    // If the next operation is not a write (or there is no next operation), send
+                    // `tx_copy` now.
+                    if next_operation_type != Some(OperationType::Write) {
+                        let pending_tx_bytes = core::mem::replace(&mut self.pending_tx_bytes, 0);
+                        return Ok(Some(Transfer::Write(
+                            &self.tx_copy[..pending_tx_bytes],
+                            final_operation,
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Copies the data of a combined read from `rx_copy` back to the buffers of its operations.
+    fn copy_back_combined_read(&mut self) {
+        if let Some(i) = self.combined_read.take() {
+            for j in (0..=i).rev() {
+                if let Operation::Read(buffer) = &mut self.operations[j] {
+                    buffer.copy_from_slice(
+                        &self.rx_copy[self.pending_rx_bytes - buffer.len()..self.pending_rx_bytes],
+                    );
+                    self.pending_rx_bytes -= buffer.len();
+                } else {
+                    break;
+                }
+            }
+
+            assert_eq!(self.pending_rx_bytes, 0);
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal-async")]
+fn read_buffer<'a>(operations: &'a mut [Operation<'_>], i: usize) -> &'a mut [u8] {
+    match &mut operations[i] {
+        Operation::Read(buffer) => buffer,
+        Operation::Write(_) => unreachable!(),
+    }
+}
+
+#[cfg(feature = "embedded-hal-async")]
+fn write_buffer<'a>(operations: &'a [Operation<'_>], i: usize) -> &'a [u8] {
+    match &operations[i] {
+        Operation::Write(buffer) => buffer,
+        Operation::Read(_) => unreachable!(),
+    }
+}
+
 impl<T: Instance> I2c for Twim<T> {
     fn transaction(
         &mut self,
@@ -586,6 +924,46 @@ impl<T: Instance> I2c for Twim<T> {
     }
 }
 
+/// Async implementation, driven by the TWIM interrupt.
+///
+/// Operations are combined in the same way as in the blocking [`I2c`] implementation. See
+/// [`Twim::on_interrupt`] for the required interrupt handler. Dropping the returned future stops
+/// the ongoing transfer.
+#[cfg(feature = "embedded-hal-async")]
+impl<T: Instance> embedded_hal_async::i2c::I2c for Twim<T> {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        compiler_fence(SeqCst);
+
+        self.0
+            .address
+            .write(|w| unsafe { w.address().bits(address) });
+
+        let mut transaction = Transaction::new(operations);
+        while let Some(transfer) = transaction.next_transfer()? {
+            // Clear events
+            self.0.events_stopped.reset();
+            self.0.events_suspended.reset();
+            self.0.events_error.reset();
+            self.0.events_lasttx.reset();
+            self.0.events_lastrx.reset();
+            self.clear_errorsrc();
+
+            match transfer {
+                Transfer::Write(buffer, final_operation) => {
+                    self.write_part_async(buffer, final_operation).await?
+                }
+                Transfer::Read(buffer) => self.read_part_async(buffer).await?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(feature = "embedded-hal-02")]
 impl<T> embedded_hal_02::blocking::i2c::Write for Twim<T>
 where
@@ -686,10 +1064,34 @@ impl i2c::Error for Error {
 pub trait Instance: Deref<Target = twim0::RegisterBlock> + sealed::Sealed {}
 
 mod sealed {
-    pub trait Sealed {}
+    #[cfg(feature = "embedded-hal-async")]
+    use super::{twim0, AtomicWaker};
+
+    pub trait Sealed {
+        #[cfg(feature = "embedded-hal-async")]
+        fn regs() -> &'static twim0::RegisterBlock;
+        #[cfg(feature = "embedded-hal-async")]
+        fn waker() -> &'static AtomicWaker;
+    }
+}
+
+macro_rules! impl_sealed {
+    ($twim:ident) => {
+        impl sealed::Sealed for $twim {
+            #[cfg(feature = "embedded-hal-async")]
+            fn regs() -> &'static twim0::RegisterBlock {
+                unsafe { &*$twim::ptr() }
+            }
+            #[cfg(feature = "embedded-hal-async")]
+            fn waker() -> &'static AtomicWaker {
+                static WAKER: AtomicWaker = AtomicWaker::new();
+                &WAKER
+            }
+        }
+    };
 }
 
-impl sealed::Sealed for TWIM0 {}
+impl_sealed!(TWIM0);
 impl Instance for TWIM0 {}
 
 #[cfg(any(
@@ -701,20 +1103,20 @@ impl Instance for TWIM0 {}
 ))]
 mod _twim1 {
     use super::*;
-    impl sealed::Sealed for TWIM1 {}
+    impl_sealed!(TWIM1);
     impl Instance for TWIM1 {}
 }
 
 #[cfg(any(feature = "9160", feature = "5340-app"))]
 mod _twim2 {
     use super::*;
-    impl sealed::Sealed for TWIM2 {}
+    impl_sealed!(TWIM2);
     impl Instance for TWIM2 {}
 }
 
 #[cfg(any(feature = "9160", feature = "5340-app"))]
 mod _twim3 {
     use super::*;
-    impl sealed::Sealed for TWIM3 {}
+    impl_sealed!(TWIM3);
     impl Instance for TWIM3 {}
 }
@@ -486,6 +486,7 @@ pub enum Error {
     RxBufferTooSmall,
     TxBufferTooLong,
     RxBufferTooLong,
+    RxBufferNotPowerOfTwo,
     Transmit,
     Receive,
     Timeout(usize),
@@ -500,6 +501,7 @@ impl fmt::Display for Error {
             RxBufferTooSmall => writeln!(f, "rx buffer too small"),
             TxBufferTooLong => writeln!(f, "tx buffer too long"),
             RxBufferTooLong => writeln!(f, "rx buffer too long"),
+            RxBufferNotPowerOfTwo => writeln!(f, "rx buffer length not a power of two"),
             Transmit => writeln!(f, "transmit error"),
             Receive => writeln!(f, "receive error"),
             Timeout(t) => writeln!(f, "timeout ({})", t),
@@ -517,6 +519,7 @@ impl embedded_io::Error for Error {
             | Self::RxBufferTooSmall
             | Self::TxBufferTooLong
             | Self::RxBufferTooLong
+            | Self::RxBufferNotPowerOfTwo
             | Self::BufferNotInRAM => ErrorKind::InvalidInput,
             Self::Transmit | Self::Receive => ErrorKind::Interrupted,
             Self::Timeout(_) => ErrorKind::TimedOut,
@@ -7,24 +7,51 @@
 //! - nrf52811: Section 4.5
 //! - nrf52832: Section 14
 //! - nrf52840: Section 4.5
+//!
+//! Apart from the customer registers, the UICR holds configuration words that are read by the
+//! chip at reset: a change to them only takes effect after the next reset, see
+//! [`Uicr::reset_required`].
+//!
+//! The nRF9160 and nRF5340 have no customer registers, but one time programmable `OTP` words. On
+//! the nRF9160 and the nRF5340 application core, the UICR can only be accessed from secure code,
+//! and is written through the secure NVMC configuration register.
+#[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
 use crate::pac::{NVMC, UICR};
+#[cfg(feature = "5340-net")]
+use crate::pac::{NVMC_NS as NVMC, UICR_NS as UICR};
+#[cfg(any(feature = "9160", feature = "5340-app"))]
+use crate::pac::{NVMC_S as NVMC, UICR_S as UICR};
 
 /// Interface to a UICR instance.
 ///
 /// This is a very basic interface that comes with the following limitations:
-/// - Only `customer` registers are usable for storing and loading of data
-/// - Erase must be performed in order to write bits with value `1` over `0`
-pub struct Uicr(UICR);
+/// - Only `customer` (or `OTP`) registers and the configuration words with a dedicated method
+///   are usable
+/// - Erase must be performed in order to write bits with value `1` over `0`, which is only
+///   available through an `ERASEALL` of the whole flash on the nRF9160 and nRF5340
+pub struct Uicr {
+    uicr: UICR,
+    reset_required: bool,
+}
 
 impl Uicr {
     /// Construct a new `Uicr` from `pac::UICR`.
     pub fn new(uicr: UICR) -> Self {
-        Self(uicr)
+        Self {
+            uicr,
+            reset_required: false,
+        }
     }
 
     /// Release the `pac::UICR` instance back.
     pub fn free(self) -> UICR {
-        self.0
+        self.uicr
+    }
+
+    /// Returns `true` if a configuration word has been changed through this `Uicr`, or the UICR
+    /// erased, so a reset is needed for the change to take effect.
+    pub fn reset_required(&self) -> bool {
+        self.reset_required
     }
 
     /// Erase the UICR registers.
@@ -32,12 +59,14 @@ impl Uicr {
     /// UICR registers can only be set to `0` bits, additional overrides back to `1` can only be
     /// performed by erasing the UICR registers.
     /// - Sets all registers to 0xFFFF_FFFFu32
+    #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
     pub fn erase(&mut self, nvmc: &mut NVMC) {
         assert!(!nvmc.config.read().wen().is_wen()); // write + erase is forbidden!
 
         nvmc.config.write(|w| w.wen().een());
         nvmc.eraseuicr.write(|w| w.eraseuicr().erase());
-        nvmc.config.reset()
+        nvmc.config.reset();
+        self.reset_required = true;
     }
 
     /// Store a slice of `&[u32]` values to the customer registers with given offset.
@@ -46,17 +75,18 @@ impl Uicr {
     /// - initial value after erase is 0xFFFF_FFFFu32
     /// - UICR registers can only be set to `0` bits, additional overrides back to `1` can only be
     ///   performed by erasing the UICR registers
+    #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
     pub fn store_customer(&mut self, nvmc: &mut NVMC, offset: usize, values: &[u32]) {
-        assert!(values.len() + offset <= self.0.customer.len()); // ensure we fit
+        assert!(values.len() + offset <= self.uicr.customer.len()); // ensure we fit
         assert!(!nvmc.config.read().wen().is_een()); // write + erase is forbidden!
 
         nvmc.config.write(|w| w.wen().wen());
         for (i, value) in values.iter().enumerate() {
             #[cfg(feature = "51")]
-            self.0.customer[offset + i].write(|w| unsafe { w.bits(*value) });
+            self.uicr.customer[offset + i].write(|w| unsafe { w.bits(*value) });
 
             #[cfg(not(feature = "51"))]
-            self.0.customer[offset + i].write(|w| unsafe { w.customer().bits(*value) });
+            self.uicr.customer[offset + i].write(|w| unsafe { w.customer().bits(*value) });
         }
         nvmc.config.reset()
     }
@@ -65,22 +95,585 @@ impl Uicr {
     ///
     /// - offset + slice length must be less than 32
     /// - returns the loaded slice
+    #[cfg(not(any(feature = "9160", feature = "5340-app", feature = "5340-net")))]
     pub fn load_customer<'a>(&mut self, offset: usize, values: &'a mut [u32]) -> &'a [u32] {
-        assert!(values.len() + offset <= self.0.customer.len()); // ensure we fit
+        assert!(values.len() + offset <= self.uicr.customer.len()); // ensure we fit
 
         let range = offset..offset + values.len();
         for (i, reg_i) in range.enumerate() {
             #[cfg(feature = "51")]
             {
-                values[i] = self.0.customer[reg_i].read().bits()
+                values[i] = self.uicr.customer[reg_i].read().bits()
             }
 
             #[cfg(not(feature = "51"))]
             {
-                values[i] = self.0.customer[reg_i].read().customer().bits()
+                values[i] = self.uicr.customer[reg_i].read().customer().bits()
             }
         }
 
         values
     }
+
+    /// Store a slice of `&[u32]` values to the `OTP` registers with given offset.
+    ///
+    /// - offset + slice length must be less than the number of `OTP` registers (190 on the
+    ///   nRF9160, 192 on the nRF5340)
+    /// - each halfword can only be written once, `Error::AlreadyWritten` is returned if the
+    ///   values change a halfword that is not erased anymore
+    /// - nothing is written if an error is returned
+    #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+    pub fn store_otp(
+        &mut self,
+        nvmc: &mut NVMC,
+        offset: usize,
+        values: &[u32],
+    ) -> Result<(), Error> {
+        assert!(values.len() + offset <= self.uicr.otp.len()); // ensure we fit
+
+        let regs = &self.uicr.otp[offset..offset + values.len()];
+        for (reg, value) in regs.iter().zip(values) {
+            otp_value(reg.read().bits(), *value)?;
+        }
+        for (reg, value) in regs.iter().zip(values) {
+            if let Some(bits) = otp_value(reg.read().bits(), *value)? {
+                write_word(nvmc, || reg.write(|w| unsafe { w.bits(bits) }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Load a slice of `&[u32]` values to the `OTP` registers from given offset.
+    ///
+    /// - offset + slice length must be less than the number of `OTP` registers
+    /// - returns the loaded slice
+    #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+    pub fn load_otp<'a>(&mut self, offset: usize, values: &'a mut [u32]) -> &'a [u32] {
+        assert!(values.len() + offset <= self.uicr.otp.len()); // ensure we fit
+
+        for (value, reg) in values.iter_mut().zip(&self.uicr.otp[offset..]) {
+            *value = reg.read().bits();
+        }
+
+        values
+    }
+
+    /// Returns the configuration of the NFC pins, P0.09 and P0.10 (P0.02 and P0.03 on the
+    /// nRF5340).
+    #[cfg(any(
+        feature = "52832",
+        feature = "52833",
+        feature = "52840",
+        feature = "5340-app"
+    ))]
+    pub fn nfc_pins(&self) -> NfcPins {
+        if self.uicr.nfcpins.read().protect().is_disabled() {
+            NfcPins::Gpio
+        } else {
+            NfcPins::Nfc
+        }
+    }
+
+    /// Returns the proof that the NFC pins are used as GPIO, to turn the `NfcPin`s of
+    /// `gpio::p0::Parts` into GPIO pins.
+    ///
+    /// Returns `None` if `UICR.NFCPINS` configures the pins for NFC, or if a configuration word has
+    /// been changed through this `Uicr` since the last reset, see [`Uicr::reset_required`].
+    #[cfg(any(
+        feature = "52832",
+        feature = "52833",
+        feature = "52840",
+        feature = "5340-app"
+    ))]
+    pub fn nfc_pins_gpio(&self) -> Option<NfcPinsGpio> {
+        if self.nfc_pins() == NfcPins::Gpio && !self.reset_required {
+            Some(NfcPinsGpio { _private: () })
+        } else {
+            None
+        }
+    }
+
+    /// Configures the NFC pins, P0.09 and P0.10 (P0.02 and P0.03 on the nRF5340), to be used as
+    /// GPIO.
+    ///
+    /// - the new configuration takes effect after the next reset
+    /// - configuring the pins back for NFC can only be performed by erasing the UICR registers
+    #[cfg(any(
+        feature = "52832",
+        feature = "52833",
+        feature = "52840",
+        feature = "5340-app"
+    ))]
+    pub fn set_nfc_pins_as_gpio(&mut self, nvmc: &mut NVMC) {
+        if self.nfc_pins() == NfcPins::Gpio {
+            return;
+        }
+        write_word(nvmc, || self.uicr.nfcpins.write(|w| w.protect().disabled()));
+        self.reset_required = true;
+    }
+
+    /// Returns `true` if the pin reset is enabled on the reset pin of the chip (P0.21, or P0.18
+    /// on the nRF52833 and nRF52840).
+    #[cfg(not(any(
+        feature = "51",
+        feature = "9160",
+        feature = "5340-app",
+        feature = "5340-net"
+    )))]
+    pub fn pin_reset_enabled(&self) -> bool {
+        self.uicr
+            .pselreset
+            .iter()
+            .all(|reg| reg.read().bits() == RESET_PIN)
+    }
+
+    /// Enables the pin reset on the reset pin of the chip, by programming both `PSELRESET`
+    /// registers.
+    ///
+    /// - the new configuration takes effect after the next reset
+    /// - disabling the pin reset again can only be performed by erasing the UICR registers
+    #[cfg(not(any(
+        feature = "51",
+        feature = "9160",
+        feature = "5340-app",
+        feature = "5340-net"
+    )))]
+    pub fn enable_pin_reset(&mut self, nvmc: &mut NVMC) -> Result<(), Error> {
+        for reg in self.uicr.pselreset.iter() {
+            if check_programmable(reg.read().bits(), RESET_PIN)? {
+                write_word(nvmc, || reg.write(|w| unsafe { w.bits(RESET_PIN) }));
+                self.reset_required = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the access port protection configuration.
+    #[cfg(not(feature = "51"))]
+    pub fn approtect(&self) -> ApProtect {
+        ApProtect::from_bits(self.uicr.approtect.read().bits())
+    }
+
+    /// Programs the access port protection configuration.
+    ///
+    /// - the new configuration takes effect after the next reset
+    /// - once enabled, the protection can only be removed by an `ERASEALL` through the debug
+    ///   interface, which also erases the flash
+    #[cfg(not(feature = "51"))]
+    pub fn set_approtect(&mut self, nvmc: &mut NVMC, approtect: ApProtect) -> Result<(), Error> {
+        let value = approtect.bits();
+        if check_programmable(self.uicr.approtect.read().bits(), value)? {
+            write_word(nvmc, || {
+                self.uicr.approtect.write(|w| unsafe { w.bits(value) })
+            });
+            self.reset_required = true;
+        }
+        Ok(())
+    }
+
+    /// Returns the access port protection configuration of the secure domain.
+    #[cfg(any(feature = "9160", feature = "5340-app"))]
+    pub fn secure_approtect(&self) -> ApProtect {
+        ApProtect::from_bits(self.uicr.secureapprotect.read().bits())
+    }
+
+    /// Programs the access port protection configuration of the secure domain.
+    ///
+    /// - the new configuration takes effect after the next reset
+    /// - once enabled, the protection can only be removed by an `ERASEALL` through the debug
+    ///   interface, which also erases the flash
+    #[cfg(any(feature = "9160", feature = "5340-app"))]
+    pub fn set_secure_approtect(
+        &mut self,
+        nvmc: &mut NVMC,
+        approtect: ApProtect,
+    ) -> Result<(), Error> {
+        let value = approtect.bits();
+        if check_programmable(self.uicr.secureapprotect.read().bits(), value)? {
+            write_word(nvmc, || {
+                self.uicr
+                    .secureapprotect
+                    .write(|w| unsafe { w.bits(value) })
+            });
+            self.reset_required = true;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the erase protection is enabled, blocking `ERASEALL` from both the CPU
+    /// and the debugger.
+    #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+    pub fn erase_protect_enabled(&self) -> bool {
+        self.uicr.eraseprotect.read().bits() != 0xffff_ffff
+    }
+
+    /// Enables the erase protection.
+    ///
+    /// - the new configuration takes effect after the next reset
+    /// - once enabled, the UICR and the flash can not be erased anymore, unless the firmware
+    ///   and the debugger agree through the `CTRL-AP` erase protection disable mechanism
+    #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+    pub fn enable_erase_protect(&mut self, nvmc: &mut NVMC) {
+        if !self.erase_protect_enabled() {
+            write_word(nvmc, || {
+                self.uicr.eraseprotect.write(|w| unsafe { w.bits(0) })
+            });
+            self.reset_required = true;
+        }
+    }
+
+    /// Returns the startup counter of the high frequency crystal oscillator: the oscillator is
+    /// considered started after `count * 64 µs + 0.5 µs`.
+    #[cfg(any(feature = "9160", feature = "5340-app"))]
+    pub fn hfxo_startup_count(&self) -> u8 {
+        self.uicr.hfxocnt.read().bits() as u8
+    }
+
+    /// Programs the startup counter of the high frequency crystal oscillator.
+    ///
+    /// - the new configuration takes effect after the next reset
+    /// - the count can only be changed to a value whose bits are a subset of the current one,
+    ///   other changes can only be performed by an `ERASEALL`
+    #[cfg(any(feature = "9160", feature = "5340-app"))]
+    pub fn set_hfxo_startup_count(&mut self, nvmc: &mut NVMC, count: u8) -> Result<(), Error> {
+        let value = 0xffff_ff00 | u32::from(count);
+        if check_programmable(self.uicr.hfxocnt.read().bits(), value)? {
+            write_word(nvmc, || {
+                self.uicr.hfxocnt.write(|w| unsafe { w.bits(value) })
+            });
+            self.reset_required = true;
+        }
+        Ok(())
+    }
+
+    /// Returns the source of the high frequency clock.
+    #[cfg(feature = "9160")]
+    pub fn hfxo_source(&self) -> HfxoSource {
+        if self.uicr.hfxosrc.read().bits() & 1 == 0 {
+            HfxoSource::Crystal
+        } else {
+            HfxoSource::Tcxo
+        }
+    }
+
+    /// Programs the source of the high frequency clock.
+    ///
+    /// - the new configuration takes effect after the next reset
+    /// - going back to the TCXO can only be performed by an `ERASEALL`
+    #[cfg(feature = "9160")]
+    pub fn set_hfxo_source(&mut self, nvmc: &mut NVMC, source: HfxoSource) -> Result<(), Error> {
+        let value = 0xffff_fffe | source as u32;
+        if check_programmable(self.uicr.hfxosrc.read().bits(), value)? {
+            write_word(nvmc, || {
+                self.uicr.hfxosrc.write(|w| unsafe { w.bits(value) })
+            });
+            self.reset_required = true;
+        }
+        Ok(())
+    }
+
+    /// Returns the output voltage of the VREGH regulator on VDD, used when the chip is supplied
+    /// through VDDH.
+    #[cfg(feature = "5340-app")]
+    pub fn vreghvout(&self) -> Regout0 {
+        Regout0::from_bits(self.uicr.vreghvout.read().bits())
+    }
+
+    /// Programs the output voltage of the VREGH regulator on VDD.
+    ///
+    /// - the new configuration takes effect after the next reset
+    /// - the voltage can only be changed to a value whose bits are a subset of the current one,
+    ///   other changes can only be performed by an `ERASEALL`
+    #[cfg(feature = "5340-app")]
+    pub fn set_vreghvout(&mut self, nvmc: &mut NVMC, voltage: Regout0) -> Result<(), Error> {
+        let value = 0xffff_fff8 | voltage as u32;
+        if check_programmable(self.uicr.vreghvout.read().bits(), value)? {
+            write_word(nvmc, || {
+                self.uicr.vreghvout.write(|w| unsafe { w.bits(value) })
+            });
+            self.reset_required = true;
+        }
+        Ok(())
+    }
+
+    /// Returns the output voltage of the REG0 regulator on VDD, used when the chip is supplied
+    /// through VDDH.
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    pub fn regout0(&self) -> Regout0 {
+        Regout0::from_bits(self.uicr.regout0.read().bits())
+    }
+
+    /// Programs the output voltage of the REG0 regulator on VDD.
+    ///
+    /// - the new configuration takes effect after the next reset
+    /// - the voltage can only be changed to a value whose bits are a subset of the current one,
+    ///   other changes can only be performed by erasing the UICR registers
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    pub fn set_regout0(&mut self, nvmc: &mut NVMC, voltage: Regout0) -> Result<(), Error> {
+        let value = 0xffff_fff8 | voltage as u32;
+        if check_programmable(self.uicr.regout0.read().bits(), value)? {
+            write_word(nvmc, || {
+                self.uicr.regout0.write(|w| unsafe { w.bits(value) })
+            });
+            self.reset_required = true;
+        }
+        Ok(())
+    }
+
+    /// Returns the debug features enabled in `DEBUGCTRL`.
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    pub fn debug_ctrl(&self) -> DebugCtrl {
+        let bits = self.uicr.debugctrl.read().bits();
+        DebugCtrl {
+            non_invasive_debug: bits & 0xff == 0xff,
+            flash_patch_breakpoint: (bits >> 8) & 0xff == 0xff,
+        }
+    }
+
+    /// Programs the debug features enabled in `DEBUGCTRL`.
+    ///
+    /// - the new configuration takes effect after the next reset
+    /// - disabled features can only be enabled again by erasing the UICR registers
+    #[cfg(any(feature = "52833", feature = "52840"))]
+    pub fn set_debug_ctrl(&mut self, nvmc: &mut NVMC, debug_ctrl: DebugCtrl) -> Result<(), Error> {
+        let mut value = 0xffff_0000;
+        if debug_ctrl.non_invasive_debug {
+            value |= 0xff;
+        }
+        if debug_ctrl.flash_patch_breakpoint {
+            value |= 0xff << 8;
+        }
+        if check_programmable(self.uicr.debugctrl.read().bits(), value)? {
+            write_word(nvmc, || {
+                self.uicr.debugctrl.write(|w| unsafe { w.bits(value) })
+            });
+            self.reset_required = true;
+        }
+        Ok(())
+    }
+}
+
+/// Checks that `current` can be changed to `value` without erasing, and returns `true` if it
+/// needs to be written.
+#[cfg(not(feature = "51"))]
+fn check_programmable(current: u32, value: u32) -> Result<bool, Error> {
+    if value & !current != 0 {
+        Err(Error::EraseRequired)
+    } else {
+        Ok(value != current)
+    }
+}
+
+/// Writes a UICR register through `write`, with writes enabled in the NVMC.
+#[cfg(not(feature = "51"))]
+fn write_word(nvmc: &mut NVMC, write: impl FnOnce()) {
+    assert!(!nvmc.config.read().wen().is_een()); // write + erase is forbidden!
+
+    nvmc.config.write(|w| w.wen().wen());
+    #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+    while !nvmc.readynext.read().readynext().bit_is_set() {}
+    write();
+    while !nvmc.ready.read().ready().bit_is_set() {}
+    nvmc.config.reset()
+}
+
+/// Returns the value to write to an `OTP` register containing `current` to store `value`, with
+/// the unchanged halfwords left erased, or `None` if `value` is already stored.
+#[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+fn otp_value(current: u32, value: u32) -> Result<Option<u32>, Error> {
+    let mut bits = 0xffff_ffff;
+    for shift in [0, 16].iter() {
+        let mask = 0xffff << shift;
+        if current & mask == value & mask {
+            continue;
+        }
+        if current & mask != mask {
+            return Err(Error::AlreadyWritten);
+        }
+        bits = (bits & !mask) | (value & mask);
+    }
+    Ok(if bits == 0xffff_ffff {
+        None
+    } else {
+        Some(bits)
+    })
+}
+
+/// `PSELRESET` value selecting the reset pin of the chip, connected.
+#[cfg(any(feature = "52833", feature = "52840"))]
+const RESET_PIN: u32 = 18;
+#[cfg(not(any(
+    feature = "51",
+    feature = "52833",
+    feature = "52840",
+    feature = "9160",
+    feature = "5340-app",
+    feature = "5340-net"
+)))]
+const RESET_PIN: u32 = 21;
+
+/// Configuration of the NFC pins, as stored in `UICR.NFCPINS`.
+#[cfg(any(
+    feature = "52832",
+    feature = "52833",
+    feature = "52840",
+    feature = "5340-app"
+))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum NfcPins {
+    /// The pins are NFC antenna pins, protected against the voltages induced by the field (the
+    /// default after an erase).
+    Nfc,
+    /// The pins are used as GPIO.
+    Gpio,
+}
+
+/// Proof that the NFC pins are used as GPIO, returned by [`Uicr::nfc_pins_gpio`].
+#[cfg(any(
+    feature = "52832",
+    feature = "52833",
+    feature = "52840",
+    feature = "5340-app"
+))]
+#[derive(Clone, Copy, Debug)]
+pub struct NfcPinsGpio {
+    _private: (),
+}
+
+/// Access port protection configuration, as stored in `UICR.APPROTECT`.
+#[cfg(not(any(
+    feature = "51",
+    feature = "9160",
+    feature = "5340-app",
+    feature = "5340-net"
+)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ApProtect {
+    /// The debugger can not access the CPU and memories.
+    Enabled = 0x00,
+    /// The protection is disabled (the default after an erase). On the chip revisions that
+    /// enable the protection by default, this is not sufficient, see `HwDisabled`.
+    Disabled = 0xff,
+    /// The hardware protection is disabled, on the chip revisions that enable the protection by
+    /// default. The firmware must also disable the protection in the `APPROTECT` peripheral at
+    /// each boot.
+    HwDisabled = 0x5a,
+}
+
+#[cfg(not(any(
+    feature = "51",
+    feature = "9160",
+    feature = "5340-app",
+    feature = "5340-net"
+)))]
+impl ApProtect {
+    fn from_bits(bits: u32) -> Self {
+        match bits as u8 {
+            0xff => ApProtect::Disabled,
+            0x5a => ApProtect::HwDisabled,
+            _ => ApProtect::Enabled,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        0xffff_ff00 | self as u32
+    }
+}
+
+/// Access port protection configuration, as stored in `UICR.APPROTECT` and
+/// `UICR.SECUREAPPROTECT`.
+#[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ApProtect {
+    /// The debugger can not access the CPU and memories (the default after an erase).
+    Enabled,
+    /// The protection is disabled. The firmware must also disable the protection in the
+    /// `APPROTECT` peripheral at each boot.
+    Disabled,
+}
+
+#[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+impl ApProtect {
+    fn from_bits(bits: u32) -> Self {
+        match bits {
+            0x50fa_50fa => ApProtect::Disabled,
+            _ => ApProtect::Enabled,
+        }
+    }
+
+    fn bits(self) -> u32 {
+        match self {
+            ApProtect::Enabled => 0x0000_0000,
+            ApProtect::Disabled => 0x50fa_50fa,
+        }
+    }
+}
+
+/// Source of the high frequency clock, as stored in `UICR.HFXOSRC`.
+#[cfg(feature = "9160")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HfxoSource {
+    /// A 32 MHz crystal.
+    Crystal = 0,
+    /// A 32 MHz temperature compensated crystal oscillator (the default after an erase).
+    Tcxo = 1,
+}
+
+/// Output voltage of the REG0 regulator, as stored in `UICR.REGOUT0` (`UICR.VREGHVOUT` for the
+/// VREGH regulator of the nRF5340).
+#[cfg(any(feature = "52833", feature = "52840", feature = "5340-app"))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Regout0 {
+    _1V8 = 0,
+    _2V1 = 1,
+    _2V4 = 2,
+    _2V7 = 3,
+    _3V0 = 4,
+    _3V3 = 5,
+    /// The default voltage, 1.8 V (the default after an erase).
+    Default = 7,
+}
+
+#[cfg(any(feature = "52833", feature = "52840", feature = "5340-app"))]
+impl Regout0 {
+    fn from_bits(bits: u32) -> Self {
+        match bits & 0x7 {
+            0 => Regout0::_1V8,
+            1 => Regout0::_2V1,
+            2 => Regout0::_2V4,
+            3 => Regout0::_2V7,
+            4 => Regout0::_3V0,
+            5 => Regout0::_3V3,
+            _ => Regout0::Default,
+        }
+    }
+}
+
+/// Debug features, as stored in `UICR.DEBUGCTRL`.
+#[cfg(any(feature = "52833", feature = "52840"))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DebugCtrl {
+    /// Non-invasive debug of the CPU (ETM and ITM trace).
+    pub non_invasive_debug: bool,
+    /// The Flash Patch and Breakpoint unit of the CPU.
+    pub flash_patch_breakpoint: bool,
+}
+
+/// UICR errors.
+#[cfg(not(feature = "51"))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// The change sets bits back to `1`, which can only be performed by erasing the UICR
+    /// registers.
+    EraseRequired,
+    /// The change writes an `OTP` halfword that has already been written.
+    #[cfg(any(feature = "9160", feature = "5340-app", feature = "5340-net"))]
+    AlreadyWritten,
 }
@@ -0,0 +1,137 @@
+// Required connections:
+//
+// - P0.28 <-> P0.29
+
+#![deny(warnings)]
+#![no_std]
+#![no_main]
+
+use defmt_rtt as _;
+use nrf52840_hal as _;
+use nrf52840_hal::gpio::Flex;
+use panic_probe as _;
+
+struct State {
+    pin_a: Flex,
+    pin_b: Flex,
+}
+
+#[defmt_test::tests]
+mod tests {
+    use cortex_m::asm;
+    use defmt::{assert, unwrap};
+    use embedded_hal::digital::{InputPin, OutputPin, StatefulOutputPin};
+    use nrf52840_hal::{
+        gpio::{p0, DriveConfig, Level, OpenDrainConfig, Pull},
+        pac,
+    };
+
+    use super::State;
+
+    #[init]
+    fn init() -> State {
+        let p = unwrap!(pac::Peripherals::take());
+        let port0 = p0::Parts::new(p.P0);
+
+        let pin_a = port0.p0_28.degrade().into_flex();
+        let pin_b = port0.p0_29.degrade().into_flex();
+
+        State { pin_a, pin_b }
+    }
+
+    #[test]
+    fn starts_disconnected(state: &mut State) {
+        assert!(!state.pin_a.is_output());
+        assert!(!state.pin_b.is_output());
+    }
+
+    #[test]
+    fn a_drives_b(state: &mut State) {
+        state.pin_b.set_as_input(Pull::None);
+        state.pin_a.set_as_output(DriveConfig::Standard0Standard1);
+        assert!(state.pin_a.is_output());
+
+        state.pin_a.set_level(Level::High);
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert!(state.pin_b.is_high().unwrap());
+
+        state.pin_a.set_level(Level::Low);
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert!(state.pin_b.is_low().unwrap());
+    }
+
+    #[test]
+    fn b_drives_a(state: &mut State) {
+        state.pin_a.set_as_input(Pull::None);
+        state.pin_b.set_as_output(DriveConfig::Standard0Standard1);
+        assert!(!state.pin_a.is_output());
+
+        state.pin_b.set_high().unwrap();
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert!(state.pin_a.is_high().unwrap());
+
+        state.pin_b.set_low().unwrap();
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert!(state.pin_a.is_low().unwrap());
+    }
+
+    #[test]
+    fn output_reads_back_its_level(state: &mut State) {
+        state.pin_b.set_as_input(Pull::None);
+        state.pin_a.set_as_output(DriveConfig::Standard0Standard1);
+
+        state.pin_a.set_high().unwrap();
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert!(state.pin_a.is_set_high().unwrap());
+        assert!(state.pin_a.is_high().unwrap());
+
+        state.pin_a.set_low().unwrap();
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert!(state.pin_a.is_set_low().unwrap());
+        assert!(state.pin_a.is_low().unwrap());
+    }
+
+    #[test]
+    fn input_pulls(state: &mut State) {
+        state.pin_b.set_as_disconnected();
+
+        state.pin_a.set_as_input(Pull::Up);
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert!(state.pin_a.is_high().unwrap());
+
+        state.pin_a.set_as_input(Pull::Down);
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert!(state.pin_a.is_low().unwrap());
+    }
+
+    #[test]
+    fn open_drain_releases_high(state: &mut State) {
+        state.pin_b.set_as_input(Pull::Down);
+        state
+            .pin_a
+            .set_as_open_drain(OpenDrainConfig::Standard0Disconnect1, Pull::None);
+
+        state.pin_a.set_high().unwrap();
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert!(state.pin_b.is_low().unwrap());
+
+        state.pin_b.set_as_input(Pull::Up);
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert!(state.pin_a.is_high().unwrap());
+
+        state.pin_a.set_low().unwrap();
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert!(state.pin_b.is_low().unwrap());
+    }
+}
@@ -0,0 +1,132 @@
+// Required connections:
+//
+// - P0.03 <-> GND
+// - P0.04 <-> VDD
+// - P0.28 <-> P0.29
+
+#![deny(warnings)]
+#![no_std]
+#![no_main]
+
+use defmt_rtt as _;
+use nrf52840_hal as _;
+use nrf52840_hal::gpio::{Floating, Input, Output, PinBus, PushPull};
+use panic_probe as _;
+
+struct State {
+    // P0.28, P0.03, P0.04
+    inputs: Option<PinBus<Input<Floating>, 3>>,
+    // P0.31, P0.29, P0.30
+    outputs: Option<PinBus<Output<PushPull>, 3>>,
+}
+
+#[defmt_test::tests]
+mod tests {
+    use cortex_m::asm;
+    use defmt::{assert_eq, unwrap};
+    use nrf52840_hal::{
+        gpio::{p0, Level, PinBus},
+        pac,
+    };
+
+    use super::State;
+
+    #[init]
+    fn init() -> State {
+        let p = unwrap!(pac::Peripherals::take());
+        let port0 = p0::Parts::new(p.P0);
+
+        let inputs = PinBus::new([
+            port0.p0_28.into_floating_input().degrade(),
+            port0.p0_03.into_floating_input().degrade(),
+            port0.p0_04.into_floating_input().degrade(),
+        ]);
+        let outputs = PinBus::new([
+            port0.p0_31.into_push_pull_output(Level::Low).degrade(),
+            port0.p0_29.into_push_pull_output(Level::Low).degrade(),
+            port0.p0_30.into_push_pull_output(Level::Low).degrade(),
+        ]);
+
+        State {
+            inputs: Some(inputs),
+            outputs: Some(outputs),
+        }
+    }
+
+    #[test]
+    fn masks(state: &mut State) {
+        let inputs = state.inputs.as_ref().unwrap();
+        let outputs = state.outputs.as_ref().unwrap();
+        assert_eq!(inputs.mask(), 1 << 28 | 1 << 3 | 1 << 4);
+        assert_eq!(outputs.mask(), 1 << 31 | 1 << 29 | 1 << 30);
+    }
+
+    #[test]
+    fn non_consecutive_read_gathers_bits(state: &mut State) {
+        let inputs = state.inputs.as_ref().unwrap();
+        let outputs = state.outputs.as_mut().unwrap();
+
+        outputs.write(0b010);
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert_eq!(inputs.read(), 0b101);
+
+        outputs.write(0b000);
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert_eq!(inputs.read(), 0b100);
+    }
+
+    #[test]
+    fn non_consecutive_write_scatters_bits(state: &mut State) {
+        let outputs = state.outputs.as_mut().unwrap();
+
+        outputs.write(0b101);
+        assert_eq!(outputs.read_output(), 0b101);
+        assert_eq!(
+            unsafe { (*pac::P0::ptr()).out.read().bits() } & outputs.mask(),
+            1 << 31 | 1 << 30
+        );
+
+        outputs.write(0b010);
+        assert_eq!(outputs.read_output(), 0b010);
+        assert_eq!(
+            unsafe { (*pac::P0::ptr()).out.read().bits() } & outputs.mask(),
+            1 << 29
+        );
+    }
+
+    #[test]
+    fn consecutive_read(state: &mut State) {
+        let [p0_28, p0_03, p0_04] = state.inputs.take().unwrap().free();
+
+        let inputs = PinBus::new([p0_03, p0_04]);
+        assert_eq!(inputs.read(), 0b10);
+
+        // Restore original bus
+        let [p0_03, p0_04] = inputs.free();
+        state.inputs = Some(PinBus::new([p0_28, p0_03, p0_04]));
+    }
+
+    #[test]
+    fn consecutive_write(state: &mut State) {
+        let [p0_31, p0_29, p0_30] = state.outputs.take().unwrap().free();
+
+        let mut outputs = PinBus::new([p0_29, p0_30]);
+        outputs.write(0b01);
+        assert_eq!(outputs.read_output(), 0b01);
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert_eq!(state.inputs.as_ref().unwrap().read() & 1, 1);
+
+        outputs.write(0b10);
+        assert_eq!(outputs.read_output(), 0b10);
+        // GPIO operations are not instantaneous so a delay is needed
+        asm::delay(100);
+        assert_eq!(state.inputs.as_ref().unwrap().read() & 1, 0);
+
+        // Restore original bus
+        let [p0_29, p0_30] = outputs.free();
+        state.outputs = Some(PinBus::new([p0_31, p0_29, p0_30]));
+    }
+}
@@ -0,0 +1,109 @@
+// Required connections:
+//
+// - P0.28 <-> P0.29
+
+#![deny(warnings)]
+#![no_std]
+#![no_main]
+
+use defmt_rtt as _;
+use nrf52840_hal as _;
+use nrf52840_hal::gpio::{Disconnected, Floating, Input, Output, Pin, PushPull};
+use panic_probe as _;
+
+struct State {
+    input_pin: Option<Pin<Input<Floating>>>,
+    output_pin: Pin<Output<PushPull>>,
+    spare_pin: Option<Pin<Disconnected>>,
+}
+
+#[defmt_test::tests]
+mod tests {
+    use defmt::{assert, assert_eq, assert_ne, unwrap};
+    use nrf52840_hal::{
+        gpio::{p0, DriveConfig, Level, Port},
+        pac,
+    };
+
+    use super::State;
+
+    // DRIVE field of PIN_CNF
+    const DRIVE_SHIFT: u32 = 8;
+    const DRIVE_MASK: u32 = 0x7;
+    const DRIVE_H0H1: u32 = 3;
+
+    #[init]
+    fn init() -> State {
+        let p = unwrap!(pac::Peripherals::take());
+        let port0 = p0::Parts::new(p.P0);
+
+        let input_pin = Some(port0.p0_28.into_floating_input().degrade());
+        let output_pin = port0.p0_29.into_push_pull_output(Level::Low).degrade();
+        let spare_pin = Some(port0.p0_30.degrade());
+
+        State {
+            input_pin,
+            output_pin,
+            spare_pin,
+        }
+    }
+
+    #[test]
+    fn snapshot_is_taken_on_port(state: &mut State) {
+        let snapshot = Port::Port0.snapshot();
+        assert!(snapshot.port() == Port::Port0);
+        assert!(state.output_pin.port() == Port::Port0);
+    }
+
+    #[test]
+    fn restore_undoes_changes(state: &mut State) {
+        let snapshot = Port::Port0.snapshot();
+
+        let input_pin = state.input_pin.take().unwrap().into_pulldown_input();
+        assert_ne!(Port::Port0.snapshot().pin_cnf(28), snapshot.pin_cnf(28));
+
+        Port::Port0.restore(&snapshot);
+        assert_eq!(Port::Port0.snapshot().pin_cnf(28), snapshot.pin_cnf(28));
+
+        // The restored configuration is the original floating input
+        state.input_pin = Some(input_pin.into_floating_input());
+    }
+
+    #[test]
+    fn active_pins_include_inputs_and_outputs(_state: &mut State) {
+        let active = Port::Port0.active_pins();
+        assert_eq!(active & (1 << 28 | 1 << 29), 1 << 28 | 1 << 29);
+    }
+
+    #[test]
+    fn disconnected_pins_are_not_active(state: &mut State) {
+        let input_pin = state.input_pin.take().unwrap().into_disconnected();
+        assert_eq!(Port::Port0.active_pins() & (1 << 28), 0);
+
+        state.input_pin = Some(input_pin.into_floating_input());
+        assert_eq!(Port::Port0.active_pins() & (1 << 28), 1 << 28);
+    }
+
+    #[test]
+    fn park_unclaimed_keeps_claimed_pins(state: &mut State) {
+        let unclaimed_pin = state
+            .spare_pin
+            .take()
+            .unwrap()
+            .into_push_pull_output_drive(Level::High, DriveConfig::HighDrive0HighDrive1);
+        assert_eq!(Port::Port0.active_pins() & (1 << 30), 1 << 30);
+
+        let claimed = 1 << 28 | 1 << 29;
+        Port::Port0.park_unclaimed(claimed);
+
+        let active = Port::Port0.active_pins();
+        assert_eq!(active & !claimed, 0);
+        assert_eq!(active & claimed, claimed);
+
+        // Only the direction, input buffer and pull are changed
+        let cnf = Port::Port0.snapshot().pin_cnf(30);
+        assert_eq!((cnf >> DRIVE_SHIFT) & DRIVE_MASK, DRIVE_H0H1);
+
+        state.spare_pin = Some(unclaimed_pin.into_disconnected());
+    }
+}